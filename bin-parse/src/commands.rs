@@ -54,7 +54,7 @@ pub async fn commands(client: &Client, report: &mut Report, args: &[String], dry
         list(client).await
     } else if args.iter().any(|arg| arg == "--bads") {
         let mut bads = HashMap::new();
-        let wiki = arma3_wiki::Wiki::load_dist();
+        let wiki = arma3_wiki::Wiki::load_dist().unwrap();
         for (_, cmd) in wiki.commands().iter() {
             let cmd_name_cased = cmd.name();
             if cmd.syntax().iter().any(|syn| {
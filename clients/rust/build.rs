@@ -1,12 +1,154 @@
+use std::path::Path;
+
 use rand::distributions::Alphanumeric;
 use rand::{Rng, thread_rng};
 
+// shared with the library, which is linted on its own
+#[allow(dead_code, unused_imports, clippy::pedantic)]
+#[path = "src/model/mod.rs"]
+mod model;
+#[path = "src/snapshot.rs"]
+mod snapshot;
+
+use model::{Command, EventHandlerNamespace, ParsedEventHandler, Version};
+use snapshot::Snapshot;
+
+/// Path to a dist tree, or a `.tar` / `.tar.gz` of one, to embed instead of fetching.
+///
+/// A relative path is resolved against this package's directory, not the workspace root.
+const DIST_ENV: &str = "ARMA3_WIKI_DIST";
+
 pub fn main() {
+    println!("cargo:rerun-if-env-changed={DIST_ENV}");
+    // only the inputs of the snapshot, so unrelated changes don't fetch the dist branch again
+    for path in ["build.rs", "src/model", "src/snapshot.rs", "snapshot"] {
+        println!("cargo:rerun-if-changed={path}");
+    }
+    let dst = {
+        let target_dir = std::env::var("OUT_DIR").unwrap();
+        Path::new(&target_dir).join("arma3-wiki")
+    };
+    let _ = std::fs::remove_dir_all(&dst);
+    let snapshot = Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshot");
+
+    if let Ok(vendor) = std::env::var(DIST_ENV) {
+        let vendor = Path::new(env!("CARGO_MANIFEST_DIR")).join(vendor);
+        println!("cargo:rerun-if-changed={}", vendor.display());
+        if let Err(e) = vendored(&vendor, &dst) {
+            panic!("Failed to embed {DIST_ENV}={}: {e}", vendor.display());
+        }
+    } else if std::env::var("CARGO_FEATURE_OFFLINE").is_ok() {
+        copy(&snapshot, &dst).unwrap();
+    } else if let Err(e) = remote(&dst) {
+        println!("cargo:warning={e}, embedding the bundled snapshot instead");
+        copy(&snapshot, &dst).unwrap();
+    }
+
+    let snapshot = compile(&dst).unwrap();
+    println!("cargo:rustc-env=ARMA3_WIKI_VERSION={}", snapshot.version);
+    std::fs::write(
+        dst.with_extension("msgpack"),
+        rmp_serde::to_vec_named(&snapshot).unwrap(),
+    )
+    .unwrap();
+}
+
+/// Parses the dist tree into the binary snapshot, recording invalid files as skipped.
+fn compile(dist: &Path) -> Result<Snapshot, String> {
+    let version = std::fs::read_to_string(dist.join("version.txt"))
+        .map_err(|e| format!("Failed to read version.txt: {e}"))?;
+    let version = Version::from_wiki(version.trim())?;
+    let mut skipped = Vec::new();
+    let mut commands: Vec<Command> = read_yaml(dist, "commands", &mut skipped);
+    commands.sort_by(|a, b| a.name().cmp(b.name()));
+    let event_handlers = EventHandlerNamespace::iter()
+        .map(|ns| {
+            let mut handlers: Vec<ParsedEventHandler> =
+                read_yaml(dist, &format!("events/{ns}"), &mut skipped);
+            handlers.sort_by(|a, b| a.id().cmp(b.id()));
+            (*ns, handlers)
+        })
+        .collect();
+    Ok(Snapshot {
+        version,
+        commands,
+        event_handlers,
+        skipped,
+    })
+}
+
+fn read_yaml<T: serde::de::DeserializeOwned>(
+    dist: &Path,
+    dir: &str,
+    skipped: &mut Vec<(String, String)>,
+) -> Vec<T> {
+    let Ok(entries) = std::fs::read_dir(dist.join(dir)) else {
+        return Vec::new();
+    };
+    let mut names = entries
+        .filter_map(|e| e.ok().map(|e| e.file_name().to_string_lossy().to_string()))
+        .filter(|name| Path::new(name).extension().is_some_and(|ext| ext == "yml"))
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let path = format!("{dir}/{name}");
+            let item = std::fs::read_to_string(dist.join(&path))
+                .map_err(|e| e.to_string())
+                .and_then(|s| serde_yaml::from_str(&s).map_err(|e| e.to_string()));
+            if let Err(e) = &item {
+                println!("cargo:warning=Skipping {path}: {e}");
+                skipped.push((path, e.clone()));
+            }
+            item.ok()
+        })
+        .collect()
+}
+
+/// Embeds an existing dist tree or tarball as-is.
+fn vendored(source: &Path, dst: &Path) -> Result<(), String> {
+    if source.is_dir() {
+        return copy(source, dst);
+    }
+    let file = std::fs::File::open(source).map_err(|e| format!("Failed to open archive: {e}"))?;
+    let name = source.to_string_lossy();
+    let unpacked = dst.with_extension("unpacked");
+    let _ = std::fs::remove_dir_all(&unpacked);
+    let result = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(&unpacked)
+    } else if name.ends_with(".tar") {
+        tar::Archive::new(file).unpack(&unpacked)
+    } else {
+        return Err("expected a directory, .tar or .tar.gz".to_string());
+    };
+    result.map_err(|e| format!("Failed to unpack archive: {e}"))?;
+    // archives from GitHub wrap the tree in a single top level directory
+    let root = if unpacked.join("version.txt").exists() {
+        unpacked.clone()
+    } else {
+        let mut dirs = std::fs::read_dir(&unpacked)
+            .map_err(|e| format!("Failed to read archive: {e}"))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.join("version.txt").exists());
+        match (dirs.next(), dirs.next()) {
+            (Some(root), None) => root,
+            _ => return Err("archive does not contain a version.txt".to_string()),
+        }
+    };
+    copy(&root, dst)?;
+    let _ = std::fs::remove_dir_all(&unpacked);
+    Ok(())
+}
+
+/// Clones or updates the dist branch from GitHub.
+fn remote(dst: &Path) -> Result<(), String> {
     use git2::Repository;
     let mut tmp = std::env::temp_dir().join("arma3-wiki");
     if std::env::var("CI").is_ok() {
         if !tmp.exists() {
-            std::fs::create_dir_all(&tmp).unwrap();
+            std::fs::create_dir_all(&tmp)
+                .map_err(|e| format!("Failed to create {}: {e}", tmp.display()))?;
         }
         let random: String = thread_rng()
             .sample_iter(&Alphanumeric)
@@ -15,58 +157,70 @@ pub fn main() {
             .collect();
         tmp.push(random);
     }
-    let repo = Repository::open(&tmp).unwrap_or_else(|_| {
-        git2::build::RepoBuilder::new()
-            .branch("dist")
-            .clone("https://github.com/acemod/arma3-wiki", &tmp)
-            .map_err(|e| format!("Failed to clone repository: {e}"))
-            .unwrap()
-    });
+    match Repository::open(&tmp) {
+        Ok(repo) => {
+            // an earlier build's clone is still a full dist tree, unlike the bundled snapshot
+            if let Err(e) = update(&repo) {
+                println!("cargo:warning={e}, embedding the last fetched dist branch instead");
+                checkout_dist(&repo)?;
+            }
+        }
+        Err(_) => {
+            git2::build::RepoBuilder::new()
+                .branch("dist")
+                .clone("https://github.com/acemod/arma3-wiki", &tmp)
+                .map_err(|e| format!("Failed to clone repository: {e}"))?;
+        }
+    }
+    copy(&tmp, dst)?;
+    if std::env::var("CI").is_ok() {
+        // we sometimes don't have permission? so don't unwrap
+        let _ = std::fs::remove_dir_all(tmp);
+    }
+    Ok(())
+}
+
+/// Fetches the dist branch and fast-forwards the local one to it.
+fn update(repo: &git2::Repository) -> Result<(), String> {
     repo.find_remote("origin")
         .and_then(|mut r| r.fetch(&["dist"], None, None))
-        .map_err(|e| format!("Failed to fetch remote: {e}"))
-        .unwrap();
+        .map_err(|e| format!("Failed to fetch remote: {e}"))?;
     let fetch_head = repo
         .find_reference("FETCH_HEAD")
-        .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))
-        .unwrap();
+        .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))?;
     let commit = repo
         .reference_to_annotated_commit(&fetch_head)
-        .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))
-        .unwrap();
+        .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))?;
     let analysis = repo
         .merge_analysis(&[&commit])
-        .map_err(|e| format!("Failed to analyze merge: {e}"))
-        .unwrap();
+        .map_err(|e| format!("Failed to analyze merge: {e}"))?;
     if !analysis.0.is_up_to_date() && analysis.0.is_fast_forward() {
         let mut reference = repo
             .find_reference("refs/heads/dist")
-            .map_err(|e| format!("Failed to find reference: {e}"))
-            .unwrap();
+            .map_err(|e| format!("Failed to find reference: {e}"))?;
         reference
             .set_target(commit.id(), "Fast-Forward")
-            .map_err(|e| format!("Failed to set reference: {e}"))
-            .unwrap();
-        repo.set_head("refs/heads/dist")
-            .map_err(|e| format!("Failed to set HEAD: {e}"))
-            .unwrap();
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
-            .map_err(|e| format!("Failed to checkout HEAD: {e}"))
-            .unwrap();
+            .map_err(|e| format!("Failed to set reference: {e}"))?;
+        checkout_dist(repo)?;
     }
-    let dst = {
-        let target_dir = std::env::var("OUT_DIR").unwrap();
-        std::path::Path::new(&target_dir).join("arma3-wiki")
-    };
-    let _ = std::fs::remove_dir_all(&dst);
+    Ok(())
+}
+
+/// Checks out the local dist branch, discarding any changes to the tree.
+fn checkout_dist(repo: &git2::Repository) -> Result<(), String> {
+    repo.set_head("refs/heads/dist")
+        .map_err(|e| format!("Failed to set HEAD: {e}"))?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+        .map_err(|e| format!("Failed to checkout HEAD: {e}"))
+}
+
+fn copy(src: &Path, dst: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dst).map_err(|e| format!("Failed to create {}: {e}", dst.display()))?;
     fs_extra::dir::copy(
-        &tmp,
+        src,
         dst,
         &fs_extra::dir::CopyOptions::new().content_only(true),
     )
-    .unwrap();
-    if std::env::var("CI").is_ok() {
-        // we sometimes don't have permission? so don't unwrap
-        let _ = std::fs::remove_dir_all(tmp);
-    }
+    .map(|_| ())
+    .map_err(|e| format!("Failed to copy {}: {e}", src.display()))
 }
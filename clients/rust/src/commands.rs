@@ -1,32 +1,326 @@
-use std::collections::HashMap;
+use hashbrown::HashMap;
 
-use crate::model::Command;
+use crate::{
+    model::Command,
+    uncased::{Key, Uncased},
+};
 
+/// The commands of the wiki, looked up by name or alias in any casing.
+#[allow(clippy::struct_field_names)]
 pub struct Commands {
-    commands: HashMap<String, Command>,
+    commands: HashMap<Key, Command>,
+    /// Aliases to the name of their command.
+    aliases: HashMap<Key, Key>,
+    /// Lowercase names in order, for prefix queries.
+    sorted: Vec<Key>,
 }
 
 impl Commands {
     #[must_use]
-    pub const fn raw(&self) -> &HashMap<String, Command> {
-        &self.commands
+    pub fn new(commands: impl IntoIterator<Item = Command>) -> Self {
+        let mut new = Self {
+            commands: HashMap::new(),
+            aliases: HashMap::new(),
+            sorted: Vec::new(),
+        };
+        for command in commands {
+            new.index_aliases(&command);
+            new.commands.insert(Key::new(command.name()), command);
+        }
+        new.sorted = new.commands.keys().cloned().collect();
+        new.sorted.sort();
+        new
     }
 
-    pub const fn raw_mut(&mut self) -> &mut HashMap<String, Command> {
-        &mut self.commands
+    #[must_use]
+    /// Gets a command by its name or one of its aliases, in any casing.
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.get(&Uncased(name)).or_else(|| {
+            self.aliases
+                .get(&Uncased(name))
+                .and_then(|key| self.commands.get(key))
+        })
     }
 
     #[must_use]
-    pub const fn new(commands: HashMap<String, Command>) -> Self {
-        Self { commands }
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
     }
 
     #[must_use]
-    pub fn get(&self, name: &str) -> Option<&Command> {
-        self.commands.get(&name.to_lowercase())
+    /// The official casing of a command, looked up by its name or one of its aliases.
+    ///
+    /// `SetDamage` and `setDammage` both return `setDamage`.
+    pub fn canonical_name(&self, name: &str) -> Option<&str> {
+        self.get(name).map(Command::name)
+    }
+
+    #[must_use]
+    /// Whether the name is an alias of another command, in any casing.
+    pub fn is_alias(&self, name: &str) -> bool {
+        !self.commands.contains_key(&Uncased(name)) && self.aliases.contains_key(&Uncased(name))
+    }
+
+    /// Inserts a command, returning the command it replaced.
+    pub fn insert(&mut self, command: Command) -> Option<Command> {
+        let key = Key::new(command.name());
+        if let Err(index) = self.sorted.binary_search(&key) {
+            self.sorted.insert(index, key.clone());
+        }
+        let replaced = self.commands.remove(&key);
+        if let Some(replaced) = &replaced {
+            self.unindex_aliases(replaced);
+        }
+        self.index_aliases(&command);
+        self.commands.insert(key, command);
+        replaced
     }
 
+    /// Removes a command by name in any casing.
+    pub fn remove(&mut self, name: &str) -> Option<Command> {
+        let name = Uncased(name);
+        if let Ok(index) = self
+            .sorted
+            .binary_search_by(|key| name.cmp_key(key).reverse())
+        {
+            self.sorted.remove(index);
+        }
+        let removed = self.commands.remove(&name);
+        if let Some(removed) = &removed {
+            self.unindex_aliases(removed);
+        }
+        removed
+    }
+
+    fn index_aliases(&mut self, command: &Command) {
+        let key = Key::new(command.name());
+        for alias in command.alias() {
+            let alias = alias_name(alias);
+            if !alias.is_empty() {
+                self.aliases.insert(Key::new(alias), key.clone());
+            }
+        }
+    }
+
+    fn unindex_aliases(&mut self, command: &Command) {
+        for alias in command.alias() {
+            let alias = Uncased(alias_name(alias));
+            if self
+                .aliases
+                .get(&alias)
+                .is_some_and(|key| Uncased(command.name()).cmp_key(key).is_eq())
+            {
+                self.aliases.remove(&alias);
+            }
+        }
+    }
+
+    /// Commands whose name starts with the prefix in any casing, ordered by name.
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Command> + 'a {
+        let prefix = Uncased(prefix);
+        let start = self
+            .sorted
+            .partition_point(|key| prefix.cmp_key(key).is_gt());
+        self.sorted[start..]
+            .iter()
+            .take_while(move |key| prefix.is_prefix_of(key))
+            .filter_map(|key| self.commands.get(key))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Iterates over the lowercase names and their commands.
     pub fn iter(&self) -> impl Iterator<Item = (&String, &Command)> {
-        self.commands.iter()
+        self.commands
+            .iter()
+            .map(|(key, command)| (key.as_string(), command))
+    }
+
+    /// Iterates over the lowercase aliases and their commands.
+    pub fn aliases(&self) -> impl Iterator<Item = (&String, &Command)> {
+        self.aliases.iter().filter_map(|(alias, key)| {
+            self.commands
+                .get(key)
+                .map(|command| (alias.as_string(), command))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A known command close to an unknown name, see [`Commands::suggest`].
+pub struct Suggestion<'a> {
+    /// The name or alias that matched, in its official casing.
+    pub name: &'a str,
+    pub command: &'a Command,
+    /// Similarity between 0 and 1, 1 being an exact match ignoring case.
+    pub score: f64,
+}
+
+impl Commands {
+    #[must_use]
+    /// The known commands and aliases closest to a name, best first.
+    ///
+    /// Candidates are ranked by edit distance, ignoring case, with a bonus for a common prefix.
+    pub fn suggest(&self, name: &str, limit: usize) -> Vec<Suggestion<'_>> {
+        let mut suggestions = Vec::new();
+        for command in self.commands.values() {
+            let candidates = std::iter::once(command.name())
+                .chain(command.alias().iter().map(|alias| alias_name(alias)));
+            for candidate in candidates {
+                let score = similarity(name, candidate);
+                if score >= SUGGEST_THRESHOLD {
+                    suggestions.push(Suggestion {
+                        name: candidate,
+                        command,
+                        score,
+                    });
+                }
+            }
+        }
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.name.cmp(b.name)));
+        suggestions.dedup_by(|a, b| a.name.eq_ignore_ascii_case(b.name));
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+const SUGGEST_THRESHOLD: f64 = 0.6;
+
+/// Normalized edit distance, boosted by the length of the common prefix.
+#[allow(clippy::cast_precision_loss)]
+fn similarity(a: &str, b: &str) -> f64 {
+    let a = a.to_ascii_lowercase();
+    let b = b.to_ascii_lowercase();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let score = 1.0 - distance(a.as_bytes(), b.as_bytes()) as f64 / longest as f64;
+    let prefix = a
+        .bytes()
+        .zip(b.bytes())
+        .take(4)
+        .take_while(|(a, b)| a == b)
+        .count();
+    (prefix as f64 * 0.1).mul_add(1.0 - score, score)
+}
+
+/// Edit distance counting insertions, deletions, substitutions and transpositions of adjacent characters.
+fn distance(a: &[u8], b: &[u8]) -> usize {
+    let mut before = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Strips the link markup the wiki sometimes wraps aliases in.
+fn alias_name(alias: &str) -> &str {
+    alias.trim_matches(|c: char| c == '[' || c == ']' || c.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Commands;
+    use crate::model::Command;
+
+    fn command(name: &str) -> Command {
+        let mut command = Command::default();
+        command.set_name(name.to_string());
+        command
+    }
+
+    #[test]
+    fn get() {
+        let commands = Commands::new(["setDamage", "setDir", "getDir"].map(command));
+        assert_eq!(commands.get("SETDAMAGE").unwrap().name(), "setDamage");
+        assert_eq!(commands.get("setdamage").unwrap().name(), "setDamage");
+        assert!(commands.get("setDamages").is_none());
+    }
+
+    #[test]
+    fn prefix() {
+        let mut commands = Commands::new(["setDamage", "setDir", "getDir", "set"].map(command));
+        let names = |commands: &Commands, prefix: &str| {
+            commands
+                .with_prefix(prefix)
+                .map(Command::name)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&commands, "SetD"), ["setDamage", "setDir"]);
+        assert_eq!(names(&commands, "set"), ["set", "setDamage", "setDir"]);
+        assert!(names(&commands, "x").is_empty());
+
+        commands.insert(command("setDate"));
+        assert!(commands.remove("SETDIR").is_some());
+        assert_eq!(names(&commands, "setd"), ["setDamage", "setDate"]);
+        assert_eq!(commands.len(), 4);
+    }
+
+    #[test]
+    fn alias() {
+        let mut set_damage = command("setDamage");
+        set_damage.add_alias("[[setDammage]]".to_string());
+        let mut commands = Commands::new([set_damage, command("getDir")]);
+        assert_eq!(commands.get("SETDAMMAGE").unwrap().name(), "setDamage");
+        assert_eq!(commands.canonical_name("SetDamage"), Some("setDamage"));
+        assert_eq!(commands.canonical_name("setdammage"), Some("setDamage"));
+        assert!(commands.is_alias("setDammage"));
+        assert!(!commands.is_alias("setDamage"));
+
+        let mut custom = command("myCommand");
+        custom.add_alias("myAlias".to_string());
+        commands.insert(custom);
+        assert_eq!(commands.canonical_name("MYALIAS"), Some("myCommand"));
+        commands.remove("myCommand");
+        assert!(commands.get("myAlias").is_none());
+    }
+
+    #[test]
+    fn suggest() {
+        let mut set_damage = command("setDamage");
+        set_damage.add_alias("setDammage".to_string());
+        let commands = Commands::new([set_damage, command("setDir"), command("getDammage")]);
+        let suggestions = commands.suggest("setDamge", 2);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].name, "setDamage");
+        assert_eq!(suggestions[1].name, "setDammage");
+        assert!(suggestions[0].score > suggestions[1].score);
+
+        let suggestions = commands.suggest("SETDIR", 5);
+        assert_eq!(suggestions[0].name, "setDir");
+        assert!((suggestions[0].score - 1.0).abs() < f64::EPSILON);
+
+        assert!(commands.suggest("hint", 5).is_empty());
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(super::distance(b"setdamage", b"setdamage"), 0);
+        assert_eq!(super::distance(b"setdamage", b"setdmaage"), 1);
+        assert_eq!(super::distance(b"setdamage", b"setdammage"), 1);
+        assert_eq!(super::distance(b"", b"abc"), 3);
     }
 }
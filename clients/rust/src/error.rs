@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+/// An error encountered while loading the wiki.
+pub enum WikiError {
+    /// A file or directory could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A file could not be decoded as YAML.
+    Yaml {
+        path: PathBuf,
+        line: Option<usize>,
+        source: serde_yaml::Error,
+    },
+    /// A file is not valid UTF-8.
+    Utf8 {
+        path: PathBuf,
+        source: std::str::Utf8Error,
+    },
+    /// A file expected in the wiki layout does not exist.
+    Missing(PathBuf),
+    /// The event handler namespace of a file is unknown.
+    Namespace { path: PathBuf, namespace: String },
+    /// The wiki version could not be parsed.
+    Version(String),
+    /// The embedded snapshot could not be decoded.
+    Snapshot(rmp_serde::decode::Error),
+    /// A file was left out of the embedded snapshot because it was invalid when the crate was built.
+    Skipped { path: PathBuf, reason: String },
+    /// No local data directory could be determined for the cache.
+    NoCacheDir,
+    #[cfg(feature = "remote")]
+    /// A git operation failed.
+    Git {
+        context: &'static str,
+        source: git2::Error,
+    },
+}
+
+impl WikiError {
+    pub(crate) fn io(path: &Path, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    pub(crate) fn yaml(path: &Path, source: serde_yaml::Error) -> Self {
+        Self::Yaml {
+            path: path.to_path_buf(),
+            line: source.location().map(|l| l.line()),
+            source,
+        }
+    }
+
+    #[cfg(feature = "remote")]
+    pub(crate) fn git(context: &'static str) -> impl FnOnce(git2::Error) -> Self {
+        move |source| Self::Git { context, source }
+    }
+
+    #[must_use]
+    /// The file the error relates to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Io { path, .. }
+            | Self::Yaml { path, .. }
+            | Self::Utf8 { path, .. }
+            | Self::Namespace { path, .. }
+            | Self::Skipped { path, .. } => Some(path),
+            Self::Missing(path) => Some(path),
+            Self::Version(_) | Self::Snapshot(_) | Self::NoCacheDir => None,
+            #[cfg(feature = "remote")]
+            Self::Git { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WikiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "Failed to read {}: {source}", path.display()),
+            Self::Yaml {
+                path,
+                line: Some(line),
+                source,
+            } => write!(f, "Failed to parse {}:{line}: {source}", path.display()),
+            Self::Yaml {
+                path,
+                line: None,
+                source,
+            } => write!(f, "Failed to parse {}: {source}", path.display()),
+            Self::Utf8 { path, source } => {
+                write!(f, "Invalid UTF-8 in {}: {source}", path.display())
+            }
+            Self::Missing(path) => write!(f, "Missing file: {}", path.display()),
+            Self::Namespace { path, namespace } => write!(
+                f,
+                "Unknown event handler namespace `{namespace}`: {}",
+                path.display()
+            ),
+            Self::Version(s) => write!(f, "Failed to parse version: {s}"),
+            Self::Snapshot(source) => write!(f, "Failed to decode the embedded snapshot: {source}"),
+            Self::Skipped { path, reason } => write!(
+                f,
+                "Left out of the embedded snapshot: {}: {reason}",
+                path.display()
+            ),
+            Self::NoCacheDir => write!(f, "Failed to find appdata directory"),
+            #[cfg(feature = "remote")]
+            Self::Git { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for WikiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Yaml { source, .. } => Some(source),
+            Self::Utf8 { source, .. } => Some(source),
+            Self::Snapshot(source) => Some(source),
+            #[cfg(feature = "remote")]
+            Self::Git { source, .. } => Some(source),
+            Self::Missing(_)
+            | Self::Namespace { .. }
+            | Self::Skipped { .. }
+            | Self::Version(_)
+            | Self::NoCacheDir => None,
+        }
+    }
+}
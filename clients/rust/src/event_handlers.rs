@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::{
+    model::{EventHandlerNamespace, ParsedEventHandler},
+    uncased::{Key, Uncased},
+};
+
+#[derive(Default)]
+struct Namespace {
+    handlers: Vec<ParsedEventHandler>,
+    index: hashbrown::HashMap<Key, usize>,
+}
+
+/// The event handlers of the wiki, grouped by namespace and looked up by id in any casing.
+pub struct EventHandlers {
+    namespaces: HashMap<EventHandlerNamespace, Namespace>,
+}
+
+impl EventHandlers {
+    #[must_use]
+    pub fn new(
+        event_handlers: impl IntoIterator<Item = (EventHandlerNamespace, Vec<ParsedEventHandler>)>,
+    ) -> Self {
+        let mut namespaces: HashMap<_, _> = EventHandlerNamespace::iter()
+            .map(|ns| (*ns, Namespace::default()))
+            .collect();
+        for (ns, handlers) in event_handlers {
+            let namespace = namespaces.entry(ns).or_default();
+            for handler in handlers {
+                namespace
+                    .index
+                    .entry(Key::new(handler.id()))
+                    .or_insert(namespace.handlers.len());
+                namespace.handlers.push(handler);
+            }
+        }
+        Self { namespaces }
+    }
+
+    #[must_use]
+    pub fn get(&self, ns: EventHandlerNamespace, id: &str) -> Option<&ParsedEventHandler> {
+        let namespace = self.namespaces.get(&ns)?;
+        namespace
+            .index
+            .get(&Uncased(id))
+            .map(|index| &namespace.handlers[*index])
+    }
+
+    #[must_use]
+    pub fn namespace(&self, ns: EventHandlerNamespace) -> &[ParsedEventHandler] {
+        self.namespaces
+            .get(&ns)
+            .map_or(&[], |namespace| &namespace.handlers)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EventHandlerNamespace, &[ParsedEventHandler])> {
+        self.namespaces
+            .iter()
+            .map(|(ns, namespace)| (*ns, namespace.handlers.as_slice()))
+    }
+}
@@ -1,27 +1,45 @@
 use std::{
-    collections::HashMap, fs::File, io::Write, path::PathBuf, str::FromStr, time::SystemTime,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
 };
 
 use commands::Commands;
-use git2::Repository;
-use model::{Command, EventHandlerNamespace, ParsedEventHandler, Version};
-use rust_embed::RustEmbed;
+use error::WikiError;
+use event_handlers::EventHandlers;
+use model::{Command, EventHandlerNamespace, Game, ParsedEventHandler, Version};
+use search::{SearchHit, SearchIndex, SearchItem, SearchTarget};
+use serde::de::DeserializeOwned;
+use snapshot::Snapshot;
+#[cfg(feature = "remote")]
+use source::GitSource;
+use source::WikiSource;
+use sqf::RequiredVersion;
 
 pub mod commands;
+pub mod error;
+pub mod event_handlers;
+#[cfg(feature = "remote")]
+pub mod loader;
 pub mod model;
+pub mod search;
+mod snapshot;
+pub mod source;
+pub mod sqf;
+mod uncased;
 
-#[derive(RustEmbed)]
-#[folder = "$OUT_DIR/arma3-wiki"]
-struct Asset;
+static SNAPSHOT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/arma3-wiki.msgpack"));
 
 pub struct Wiki {
     version: Version,
-    // commands: HashMap<String, Command>,
     commands: Commands,
-    event_handlers: HashMap<EventHandlerNamespace, Vec<ParsedEventHandler>>,
+    event_handlers: EventHandlers,
     custom: Vec<String>,
     /// Whether the wiki was just updated.
     updated: bool,
+    /// Built by the loaders, dropped whenever the commands change and rebuilt on the next search.
+    search: OnceLock<SearchIndex>,
 }
 
 impl Wiki {
@@ -36,7 +54,7 @@ impl Wiki {
     }
 
     #[must_use]
-    pub const fn event_handlers(&self) -> &HashMap<EventHandlerNamespace, Vec<ParsedEventHandler>> {
+    pub const fn event_handlers(&self) -> &EventHandlers {
         &self.event_handlers
     }
 
@@ -45,19 +63,24 @@ impl Wiki {
         self.updated
     }
 
-    #[must_use]
-    pub fn load(force_pull: bool) -> Self {
+    /// Loads the wiki, preferring the remote repository and falling back to the embedded assets.
+    ///
+    /// # Errors
+    /// Returns an error if neither source could be loaded.
+    pub fn load(force_pull: bool) -> Result<Self, WikiError> {
         #[cfg(feature = "remote")]
         if let Ok(a3wiki) = Self::load_git(force_pull) {
-            return a3wiki;
+            return Ok(a3wiki);
         }
+        #[cfg(not(feature = "remote"))]
+        let _ = force_pull;
         Self::load_dist()
     }
 
     pub fn add_custom_command(&mut self, command: Command) {
-        let name = command.name().to_lowercase();
-        self.custom.push(name.clone());
-        self.commands.raw_mut().insert(name, command);
+        self.custom.push(command.name().to_lowercase());
+        self.commands.insert(command);
+        self.search.take();
     }
 
     /// Adds a custom command to the wiki.
@@ -71,10 +94,11 @@ impl Wiki {
     }
 
     pub fn remove_command(&mut self, name: &str) -> bool {
-        let name = name.to_lowercase();
-        self.commands.raw_mut().remove(&name);
-        if self.custom.contains(&name) {
-            self.custom.retain(|c| c != &name);
+        if self.commands.remove(name).is_some() {
+            self.search.take();
+        }
+        if self.is_custom_command(name) {
+            self.custom.retain(|c| !c.eq_ignore_ascii_case(name));
             true
         } else {
             false
@@ -83,209 +107,222 @@ impl Wiki {
 
     #[must_use]
     pub fn is_custom_command(&self, name: &str) -> bool {
-        self.custom.contains(&name.to_lowercase())
+        self.custom.iter().any(|c| c.eq_ignore_ascii_case(name))
     }
 
     #[must_use]
     pub fn event_handler(&self, id: &str) -> Vec<(EventHandlerNamespace, &ParsedEventHandler)> {
-        let mut handlers = Vec::new();
-        for (ns, hs) in &self.event_handlers {
-            if let Some(handler) = hs.iter().find(|h| h.id() == id) {
-                handlers.push((*ns, handler));
-            }
+        EventHandlerNamespace::iter()
+            .filter_map(|ns| self.event_handlers.get(*ns, id).map(|h| (*ns, h)))
+            .collect()
+    }
+
+    #[must_use]
+    /// A copy of the wiki with only what is available in a version of a game.
+    ///
+    /// Commands, syntaxes, params, alternative types and event handlers newer than the version are left out.
+    pub fn at_version(&self, game: Game, version: &Version) -> Self {
+        let commands = Commands::new(
+            self.commands
+                .iter()
+                .filter_map(|(_, command)| command.at_version(game, version)),
+        );
+        let event_handlers =
+            EventHandlers::new(self.event_handlers.iter().map(|(ns, handlers)| {
+                (
+                    ns,
+                    handlers
+                        .iter()
+                        .filter_map(|handler| handler.at_version(game, version))
+                        .collect(),
+                )
+            }));
+        Self {
+            version: self.version,
+            custom: self
+                .custom
+                .iter()
+                .filter(|name| commands.contains(name))
+                .cloned()
+                .collect(),
+            commands,
+            event_handlers,
+            updated: self.updated,
+            search: OnceLock::new(),
         }
-        handlers
+    }
+
+    #[must_use]
+    /// Searches the documentation of commands and event handlers, best matches first.
+    ///
+    /// The index is built when the wiki is loaded. After custom commands are added or removed,
+    /// or for a copy from [`Self::at_version`], the next search rebuilds it first.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+        self.index()
+            .search(query)
+            .into_iter()
+            .filter_map(|(target, score)| {
+                let item = match target {
+                    SearchTarget::Command(name) => SearchItem::Command(self.commands.get(name)?),
+                    SearchTarget::EventHandler(ns, id) => {
+                        SearchItem::EventHandler(*ns, self.event_handlers.get(*ns, id)?)
+                    }
+                };
+                Some(SearchHit { item, score })
+            })
+            .collect()
+    }
+
+    fn index(&self) -> &SearchIndex {
+        self.search
+            .get_or_init(|| SearchIndex::new(&self.commands, &self.event_handlers))
+    }
+
+    #[must_use]
+    /// The minimum Arma 3 version needed to run a piece of SQF, with the items that require it.
+    pub fn required_version(&self, source: &str) -> RequiredVersion<'_> {
+        RequiredVersion::new(&self.commands, &self.event_handlers, source)
     }
 
     #[cfg(feature = "remote")]
     /// Loads the wiki from the remote repository.
     ///
     /// # Errors
-    /// Returns an error if the repository could not be cloned or fetched.
-    ///
-    /// # Panics
-    /// Panics if the structure of the repository is invalid, attempts to delete it and fresh clone it before panicking.
-    pub fn load_git(force_pull: bool) -> Result<Self, String> {
-        std::panic::catch_unwind(|| Self::attempt_load_git(force_pull)).unwrap_or_else(|_| {
-            let appdata = get_appdata();
-            std::fs::remove_dir_all(&appdata).unwrap_or_default();
-            Self::attempt_load_git(force_pull)
-        })
+    /// Returns an error if the repository could not be cloned, or if any file in it is invalid.
+    pub fn load_git(force_pull: bool) -> Result<Self, WikiError> {
+        Self::load_source(&GitSource::open(force_pull)?)
     }
 
     #[cfg(feature = "remote")]
-    /// Loads the wiki from the remote repository.
+    /// Loads the wiki from the remote repository, skipping invalid files.
+    ///
+    /// The skipped files are returned alongside the wiki.
     ///
     /// # Errors
-    /// Returns an error if the repository could not be cloned or fetched.
+    /// Returns an error if the repository could not be cloned, or its layout is invalid.
+    pub fn load_git_partial(force_pull: bool) -> Result<(Self, Vec<WikiError>), WikiError> {
+        Self::load_source_partial(&GitSource::open(force_pull)?)
+    }
+
+    /// Loads the wiki from the snapshot embedded at build time.
     ///
-    /// # Panics
-    /// Panics if the structure of the repository is invalid.
-    fn attempt_load_git(force_pull: bool) -> Result<Self, String> {
-        let appdata = get_appdata();
-        let updated = if !force_pull && Self::recently_updated(&appdata) {
-            false
+    /// # Errors
+    /// Returns an error if the snapshot could not be decoded, or if any file was invalid when it was built.
+    pub fn load_dist() -> Result<Self, WikiError> {
+        let (wiki, mut diagnostics) = Self::load_dist_partial()?;
+        if diagnostics.is_empty() {
+            Ok(wiki)
         } else {
-            let repo = if let Ok(repo) = Repository::open(&appdata) {
-                repo
-            } else {
-                git2::build::RepoBuilder::new()
-                    .branch("dist")
-                    .clone("https://github.com/acemod/arma3-wiki", &appdata)
-                    .map_err(|e| format!("Failed to clone repository: {e}"))?
-            };
-            Self::update_git(&repo).is_ok()
-        };
-        let mut commands = HashMap::new();
-        for entry in std::fs::read_dir(appdata.join("commands")).unwrap() {
-            let path = entry.unwrap().path();
-            if path.is_file() {
-                let command: Command = serde_yaml::from_reader(std::fs::File::open(&path).unwrap())
-                    .unwrap_or_else(|_| {
-                        panic!("Failed to parse command: {path}", path = path.display())
-                    });
-                commands.insert(command.name().to_lowercase(), command);
-            }
-        }
-        let mut event_handlers = HashMap::new();
-        for ns in EventHandlerNamespace::iter() {
-            let mut handlers = Vec::new();
-            for entry in std::fs::read_dir(appdata.join("events").join(ns.to_string())).unwrap() {
-                let path = entry.unwrap().path();
-                if path.is_file() {
-                    let handler: ParsedEventHandler =
-                        serde_yaml::from_reader(std::fs::File::open(&path).unwrap())
-                            .unwrap_or_else(|_| {
-                                panic!(
-                                    "Failed to parse event handler: {path}",
-                                    path = path.display()
-                                )
-                            });
-                    handlers.push(handler);
-                }
-            }
-            event_handlers.insert(*ns, handlers);
+            Err(diagnostics.swap_remove(0))
         }
-        Ok(Self {
-            version: Version::from_wiki(
-                std::fs::read_to_string(appdata.join("version.txt"))
-                    .unwrap()
-                    .trim(),
-            )
-            .map_err(|e| format!("Failed to parse version: {e}"))?,
-            commands: Commands::new(commands),
-            event_handlers,
-            updated,
+    }
+
+    /// Loads the wiki from the snapshot embedded at build time.
+    ///
+    /// The files the build script left out as invalid are returned alongside the wiki.
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot could not be decoded.
+    pub fn load_dist_partial() -> Result<(Self, Vec<WikiError>), WikiError> {
+        let snapshot: Snapshot = rmp_serde::from_slice(SNAPSHOT).map_err(WikiError::Snapshot)?;
+        let diagnostics = snapshot
+            .skipped
+            .into_iter()
+            .map(|(path, reason)| WikiError::Skipped {
+                path: PathBuf::from(path),
+                reason,
+            })
+            .collect();
+        let wiki = Self {
+            version: snapshot.version,
+            commands: Commands::new(snapshot.commands),
+            event_handlers: EventHandlers::new(snapshot.event_handlers),
+            updated: false,
             custom: Vec::new(),
-        })
+            search: OnceLock::new(),
+        };
+        wiki.index();
+        Ok((wiki, diagnostics))
     }
 
     #[must_use]
-    /// Loads the wiki from the embedded assets.
+    /// The version of the snapshot embedded at build time, without decoding it.
+    pub fn dist_version() -> Option<Version> {
+        Version::from_wiki(env!("ARMA3_WIKI_VERSION")).ok()
+    }
+
+    /// Loads the wiki from a source.
     ///
-    /// # Panics
-    /// Panics if the assets are not found.
-    pub fn load_dist() -> Self {
-        let mut commands = HashMap::new();
-        let mut event_handlers = HashMap::new();
-        for entry in Asset::iter() {
-            let path = entry.as_ref();
-            if path.starts_with("commands/")
-                && std::path::Path::new(path)
-                    .extension()
-                    .is_some_and(|ext| ext.eq_ignore_ascii_case("yml"))
+    /// # Errors
+    /// Returns an error if the source could not be read, or if any file in it is invalid.
+    pub fn load_source<S: WikiSource + ?Sized>(source: &S) -> Result<Self, WikiError> {
+        let (wiki, mut diagnostics) = Self::load_source_partial(source)?;
+        if diagnostics.is_empty() {
+            Ok(wiki)
+        } else {
+            Err(diagnostics.swap_remove(0))
+        }
+    }
+
+    /// Loads the wiki from a source, skipping invalid files.
+    ///
+    /// The skipped files are returned alongside the wiki.
+    ///
+    /// # Errors
+    /// Returns an error if the source could not be listed, or its version is missing or invalid.
+    pub fn load_source_partial<S: WikiSource + ?Sized>(
+        source: &S,
+    ) -> Result<(Self, Vec<WikiError>), WikiError> {
+        let mut diagnostics = Vec::new();
+        let mut commands = Vec::new();
+        let mut event_handlers: HashMap<_, Vec<_>> = HashMap::new();
+        for path in source.files()? {
+            if !Path::new(&path)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("yml"))
             {
-                let command: Command = serde_yaml::from_str(
-                    std::str::from_utf8(Asset::get(path).unwrap().data.as_ref()).unwrap(),
-                )
-                .unwrap();
-                commands.insert(command.name().to_lowercase(), command);
-            } else if path.starts_with("events/") {
-                let parts: Vec<&str> = path.split('/').collect();
-                if parts.len() == 3 {
-                    let ns = EventHandlerNamespace::from_str(parts[1]).unwrap();
-                    let handler: ParsedEventHandler = serde_yaml::from_str(
-                        std::str::from_utf8(Asset::get(path).unwrap().data.as_ref()).unwrap(),
-                    )
-                    .unwrap();
-                    event_handlers
-                        .entry(ns)
-                        .or_insert_with(Vec::new)
-                        .push(handler);
+                continue;
+            }
+            let parts: Vec<&str> = path.split('/').collect();
+            match parts.as_slice() {
+                ["commands", _] => match read_yaml::<Command, _>(source, &path) {
+                    Ok(command) => commands.push(command),
+                    Err(e) => diagnostics.push(e),
+                },
+                ["events", ns, _] => {
+                    let Ok(ns) = EventHandlerNamespace::from_str(ns) else {
+                        diagnostics.push(WikiError::Namespace {
+                            path: PathBuf::from(&path),
+                            namespace: (*ns).to_string(),
+                        });
+                        continue;
+                    };
+                    match read_yaml::<ParsedEventHandler, _>(source, &path) {
+                        Ok(handler) => event_handlers.entry(ns).or_default().push(handler),
+                        Err(e) => diagnostics.push(e),
+                    }
                 }
+                _ => {}
             }
         }
-        Self {
-            version: Version::from_wiki(
-                std::str::from_utf8(Asset::get("version.txt").unwrap().data.as_ref())
-                    .unwrap()
-                    .trim(),
-            )
-            .unwrap(),
+        let wiki = Self {
+            version: Version::from_wiki(source.read_to_string("version.txt")?.trim())
+                .map_err(WikiError::Version)?,
             commands: Commands::new(commands),
-            event_handlers,
-            updated: false,
+            event_handlers: EventHandlers::new(event_handlers),
+            updated: source.updated(),
             custom: Vec::new(),
-        }
-    }
-
-    fn update_git(repo: &Repository) -> Result<(), String> {
-        repo.find_remote("origin")
-            .and_then(|mut r| r.fetch(&["dist"], None, None))
-            .map_err(|e| format!("Failed to fetch remote: {e}"))?;
-        let fetch_head = repo
-            .find_reference("FETCH_HEAD")
-            .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))?;
-        let commit = repo
-            .reference_to_annotated_commit(&fetch_head)
-            .map_err(|e| format!("Failed to find FETCH_HEAD: {e}"))?;
-        let analysis = repo
-            .merge_analysis(&[&commit])
-            .map_err(|e| format!("Failed to analyze merge: {e}"))?;
-        if !analysis.0.is_up_to_date() && analysis.0.is_fast_forward() {
-            let mut reference = repo
-                .find_reference("refs/heads/dist")
-                .map_err(|e| format!("Failed to find reference: {e}"))?;
-            reference
-                .set_target(commit.id(), "Fast-Forward")
-                .map_err(|e| format!("Failed to set reference: {e}"))?;
-            repo.set_head("refs/heads/dist")
-                .map_err(|e| format!("Failed to set HEAD: {e}"))?;
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
-                .map_err(|e| format!("Failed to checkout HEAD: {e}"))?;
-        }
-        let Ok(mut file) = File::create(get_appdata().join("last-update.timestamp")) else {
-            return Ok(());
+            search: OnceLock::new(),
         };
-        let _ = file.write_all(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                .to_string()
-                .as_bytes(),
-        );
-        Ok(())
-    }
-
-    fn recently_updated(path: &std::path::Path) -> bool {
-        if let Ok(timestamp) = std::fs::read_to_string(path.join("last-update.timestamp"))
-            && let Ok(timestamp) = timestamp.parse::<u64>()
-            && let Ok(elapsed) = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .map(|t| t.as_secs())
-        {
-            // Update every 6 hours
-            return elapsed - timestamp < 60 * 60 * 6;
-        }
-        false
+        wiki.index();
+        Ok((wiki, diagnostics))
     }
 }
 
-fn get_appdata() -> PathBuf {
-    let dirs = directories::ProjectDirs::from("org", "acemod", "arma3-wiki")
-        .expect("Failed to find appdata directory");
-    let appdata = dirs.data_local_dir();
-    std::fs::create_dir_all(appdata).unwrap();
-    appdata.to_path_buf()
+fn read_yaml<T: DeserializeOwned, S: WikiSource + ?Sized>(
+    source: &S,
+    path: &str,
+) -> Result<T, WikiError> {
+    serde_yaml::from_str(&source.read_to_string(path)?)
+        .map_err(|e| WikiError::yaml(Path::new(path), e))
 }
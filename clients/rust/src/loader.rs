@@ -0,0 +1,285 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    Wiki,
+    error::WikiError,
+    model::Version,
+    source::{GitSource, WikiSource},
+};
+
+pub const DEFAULT_URL: &str = "https://github.com/acemod/arma3-wiki";
+pub const DEFAULT_BRANCH: &str = "dist";
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_hours(6);
+
+#[derive(Clone, Debug)]
+/// Configures where and how the wiki is loaded from git.
+///
+/// ```no_run
+/// # use arma3_wiki::loader::WikiLoader;
+/// let wiki = WikiLoader::new()
+///     .url("file:///srv/mirrors/arma3-wiki")
+///     .revision("v2.18")
+///     .load()
+///     .unwrap();
+/// ```
+pub struct WikiLoader {
+    url: String,
+    branch: String,
+    revision: Option<String>,
+    cache_dir: Option<PathBuf>,
+    refresh_interval: Duration,
+    force_pull: bool,
+    prefer_newest: bool,
+}
+
+impl Default for WikiLoader {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_URL.to_string(),
+            branch: DEFAULT_BRANCH.to_string(),
+            revision: None,
+            cache_dir: None,
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            force_pull: false,
+            prefer_newest: false,
+        }
+    }
+}
+
+impl WikiLoader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// The remote to clone from, any URL supported by git, including `file://`.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    #[must_use]
+    /// The branch to track, `dist` by default.
+    pub fn branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = branch.into();
+        self
+    }
+
+    #[must_use]
+    /// A commit or tag to check out instead of the tip of the branch.
+    pub fn revision(mut self, revision: impl Into<String>) -> Self {
+        self.revision = Some(revision.into());
+        self
+    }
+
+    #[must_use]
+    /// The directory to keep the clone in, the local appdata directory by default.
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    #[must_use]
+    /// How long a clone is used before fetching again, 6 hours by default.
+    pub const fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    #[must_use]
+    /// Fetch even if the clone was updated within the refresh interval.
+    pub const fn force_pull(mut self, force_pull: bool) -> Self {
+        self.force_pull = force_pull;
+        self
+    }
+
+    #[must_use]
+    /// Use the embedded snapshot instead of the clone if their `version.txt` is newer.
+    pub const fn prefer_newest(mut self, prefer_newest: bool) -> Self {
+        self.prefer_newest = prefer_newest;
+        self
+    }
+
+    #[must_use]
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    #[must_use]
+    pub fn get_branch(&self) -> &str {
+        &self.branch
+    }
+
+    #[must_use]
+    pub fn get_revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    #[must_use]
+    pub fn get_cache_dir(&self) -> Option<&std::path::Path> {
+        self.cache_dir.as_deref()
+    }
+
+    #[must_use]
+    pub const fn get_refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    #[must_use]
+    pub const fn get_force_pull(&self) -> bool {
+        self.force_pull
+    }
+
+    /// Clones or updates the repository.
+    ///
+    /// # Errors
+    /// Returns an error if the repository could not be cloned, or the revision could not be checked out.
+    pub fn open_git(&self) -> Result<GitSource, WikiError> {
+        GitSource::sync(self)
+    }
+
+    /// Loads the wiki.
+    ///
+    /// # Errors
+    /// Returns an error if the wiki could not be loaded, or if any file in it is invalid.
+    pub fn load(&self) -> Result<Wiki, WikiError> {
+        if self.prefer_newest {
+            let Ok(git) = self.open_git() else {
+                return Wiki::load_dist();
+            };
+            if Self::embedded_is_newer(&git) {
+                Wiki::load_dist()
+            } else {
+                Wiki::load_source(&git)
+            }
+        } else {
+            Wiki::load_source(&self.open_git()?)
+        }
+    }
+
+    /// Loads the wiki, skipping invalid files.
+    ///
+    /// The skipped files are returned alongside the wiki.
+    ///
+    /// # Errors
+    /// Returns an error if the wiki could not be loaded, or its layout is invalid.
+    pub fn load_partial(&self) -> Result<(Wiki, Vec<WikiError>), WikiError> {
+        if self.prefer_newest {
+            let Ok(git) = self.open_git() else {
+                return Wiki::load_dist_partial();
+            };
+            if Self::embedded_is_newer(&git) {
+                Wiki::load_dist_partial()
+            } else {
+                Wiki::load_source_partial(&git)
+            }
+        } else {
+            Wiki::load_source_partial(&self.open_git()?)
+        }
+    }
+
+    /// Compares `version.txt`, the clone wins a tie or an unreadable version.
+    fn embedded_is_newer(git: &GitSource) -> bool {
+        let cached = git
+            .read_to_string("version.txt")
+            .ok()
+            .and_then(|v| Version::from_wiki(v.trim()).ok());
+        match (Wiki::dist_version(), cached) {
+            (Some(embedded), Some(git)) => embedded > git,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use git2::{Repository, Signature};
+
+    use super::WikiLoader;
+    use crate::model::Version;
+
+    fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents.iter().collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let target = to.join(path.file_name().unwrap());
+            if path.is_dir() {
+                copy_dir(&path, &target);
+            } else {
+                std::fs::copy(&path, &target).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn pinned_revision() {
+        let root = std::env::temp_dir().join(format!("arma3-wiki-loader-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let origin = root.join("origin");
+        copy_dir(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dist"),
+            &origin,
+        );
+        let repo = Repository::init_opts(
+            &origin,
+            git2::RepositoryInitOptions::new().initial_head("dist"),
+        )
+        .unwrap();
+        let first = commit(&repo, "2.18");
+        repo.tag_lightweight("v2.18", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        std::fs::write(origin.join("version.txt"), "2.20\n").unwrap();
+        commit(&repo, "2.20");
+
+        let loader = WikiLoader::new()
+            .url(format!("file://{}", origin.display()))
+            .cache_dir(root.join("cache"));
+        let latest = loader.load().unwrap();
+        assert_eq!(latest.version(), &Version::new(2, 20));
+        let pinned = loader.clone().revision("v2.18").load().unwrap();
+        assert_eq!(pinned.version(), &Version::new(2, 18));
+        let pinned = loader.clone().revision(first.to_string()).load().unwrap();
+        assert_eq!(pinned.version(), &Version::new(2, 18));
+        // returning to the branch ignores the refresh interval
+        let latest = loader.load().unwrap();
+        assert_eq!(latest.version(), &Version::new(2, 20));
+
+        // offline, removing the pin falls back to the cached branch rather than the old pin
+        loader.clone().revision("v2.18").load().unwrap();
+        std::fs::rename(&origin, root.join("offline")).unwrap();
+        let offline = loader.load().unwrap();
+        assert_eq!(offline.version(), &Version::new(2, 20));
+        assert!(!offline.updated());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,226 @@
+use super::{Arg, Call, Param};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The operands of a [`super::Syntax`], with each argument resolved to its [`Param`].
+pub enum Bindings<'a> {
+    Nular,
+    Unary(Binding<'a>),
+    Binary(Binding<'a>, Binding<'a>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// An [`Arg`] resolved to the params of its syntax.
+pub enum Binding<'a> {
+    Param(&'a Param),
+    /// An argument without a documented param.
+    Unresolved(&'a str),
+    Array(Vec<Self>),
+    Optional(Box<Self>),
+    Variadic,
+}
+
+impl<'a> Bindings<'a> {
+    pub(super) fn new(call: &'a Call, find: &impl Fn(&str) -> Option<&'a Param>) -> Self {
+        match call {
+            Call::Nular => Self::Nular,
+            Call::Unary(arg) => Self::Unary(Binding::new(arg, find)),
+            Call::Binary(left, right) => {
+                Self::Binary(Binding::new(left, find), Binding::new(right, find))
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn left(&self) -> Option<&Binding<'a>> {
+        match self {
+            Self::Binary(left, _) => Some(left),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn right(&self) -> Option<&Binding<'a>> {
+        match self {
+            Self::Unary(right) | Self::Binary(_, right) => Some(right),
+            Self::Nular => None,
+        }
+    }
+}
+
+impl<'a> Binding<'a> {
+    fn new(arg: &'a Arg, find: &impl Fn(&str) -> Option<&'a Param>) -> Self {
+        match arg {
+            Arg::Item(name) => find(name).map_or(Self::Unresolved(name), Self::Param),
+            Arg::Array(args) => Self::Array(args.iter().map(|arg| Self::new(arg, find)).collect()),
+            Arg::Optional(arg) => Self::Optional(Box::new(Self::new(arg, find))),
+            Arg::Variadic => Self::Variadic,
+        }
+    }
+
+    #[must_use]
+    /// The param of a single argument, looking through `Optional`.
+    pub fn param(&self) -> Option<&'a Param> {
+        match self {
+            Self::Param(param) => Some(param),
+            Self::Optional(binding) => binding.param(),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    /// The element at `index` of an array argument given with `len` elements.
+    ///
+    /// Elements past a `Variadic` are bound to the element repeated before it, except for the last
+    /// ones, which are bound to the elements documented after it, such as `forceRadio` in
+    /// `[sender, topicName, sentenceClass, argumentName1, ..., forceRadio]`.
+    pub fn get(&self, index: usize, len: usize) -> Option<&Self> {
+        match self {
+            Self::Array(elements) if index < len => {
+                match elements.iter().position(|e| e == &Self::Variadic) {
+                    Some(variadic) if index >= variadic => {
+                        let trailing = elements.len() - variadic - 1;
+                        match (index + trailing).checked_sub(len) {
+                            Some(from_end) => elements.get(variadic + 1 + from_end),
+                            None => elements.get(variadic.checked_sub(1)?),
+                        }
+                    }
+                    _ => elements.get(index),
+                }
+            }
+            Self::Optional(binding) => binding.get(index, len),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    /// The element at a path of documented positions in nested arrays, such as `[1, 0]` for `row`
+    /// in `[idc, [row, column], colour]`.
+    pub fn at(&self, path: &[usize]) -> Option<&Self> {
+        path.iter()
+            .try_fold(self, |binding, &index| binding.element(index))
+    }
+
+    fn element(&self, index: usize) -> Option<&Self> {
+        match self {
+            Self::Array(elements) => elements.get(index),
+            Self::Optional(binding) => binding.element(index),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Binding;
+    use crate::model::{Arg, Call, Param, Syntax, Value};
+
+    fn param(name: &str, typ: Value, optional: bool) -> Param {
+        Param::new(name.to_string(), None, typ, optional, None, None)
+    }
+
+    #[test]
+    fn bindings() {
+        let call = Call::Binary(
+            Arg::Item("control".to_string()),
+            Call::parse_params("[idc, [row, column], colour]").unwrap(),
+        );
+        let syntax = Syntax::new(
+            call,
+            (Value::Nothing, None),
+            vec![
+                param("control", Value::Control, false),
+                param("idc", Value::Number, false),
+                param("row", Value::Number, false),
+                param("column", Value::Number, false),
+                param("colour", Value::ArrayColor, true),
+            ],
+            None,
+            None,
+        );
+        let bindings = syntax.bindings();
+        assert_eq!(
+            bindings.left().and_then(Binding::param).map(Param::typ),
+            Some(&Value::Control)
+        );
+        let right = bindings.right().unwrap();
+        assert_eq!(
+            right.at(&[1, 0]).and_then(Binding::param).map(Param::name),
+            Some("row")
+        );
+        assert_eq!(
+            right.get(2, 3).and_then(Binding::param).map(Param::name),
+            Some("colour")
+        );
+        assert_eq!(right.get(3, 4), None);
+        assert_eq!(right.get(2, 2), None);
+        assert_eq!(right.at(&[0, 0]), None);
+    }
+
+    #[test]
+    fn variadic() {
+        let syntax = Syntax::new(
+            Call::Unary(Call::parse_params("[format, argument1, ...]").unwrap()),
+            (Value::String, None),
+            vec![
+                param("format", Value::String, false),
+                param("argumentN", Value::Anything, true),
+            ],
+            None,
+            None,
+        );
+        let bindings = syntax.bindings();
+        let right = bindings.right().unwrap();
+        assert_eq!(bindings.left(), None);
+        assert_eq!(
+            right.get(5, 6).and_then(Binding::param).map(Param::name),
+            Some("argumentN")
+        );
+
+        // kbTell
+        let syntax = Syntax::new(
+            Call::Binary(
+                Arg::Item("receiver".to_string()),
+                Call::parse_params(
+                    "[sender, topicName, sentenceClass, argumentName1, ..., forceRadio]",
+                )
+                .unwrap(),
+            ),
+            (Value::Nothing, None),
+            vec![
+                param("receiver", Value::Object, false),
+                param("sender", Value::Object, false),
+                param("topicName", Value::String, false),
+                param("sentenceClass", Value::String, false),
+                param("argumentNameN", Value::String, true),
+                param("forceRadio", Value::Boolean, true),
+            ],
+            None,
+            None,
+        );
+        let bindings = syntax.bindings();
+        let right = bindings.right().unwrap();
+        let name = |index, len| {
+            right
+                .get(index, len)
+                .and_then(Binding::param)
+                .map(Param::name)
+        };
+        assert_eq!(name(3, 6), Some("argumentNameN"));
+        assert_eq!(name(4, 6), Some("argumentNameN"));
+        assert_eq!(name(5, 6), Some("forceRadio"));
+        assert_eq!(name(4, 5), Some("forceRadio"));
+        assert_eq!(name(6, 6), None);
+
+        let syntax = Syntax::new(
+            Call::Unary(Arg::Item("value".to_string())),
+            (Value::Nothing, None),
+            Vec::new(),
+            None,
+            None,
+        );
+        assert_eq!(
+            syntax.bindings().right(),
+            Some(&Binding::Unresolved("value"))
+        );
+    }
+}
@@ -1,10 +1,49 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(from = "ArgRepr", into = "ArgRepr")]
 pub enum Arg {
     Item(String),
-    Array(Vec<Arg>),
+    Array(Vec<Self>),
+    /// An array element that may be left out, along with every element after it.
+    Optional(Box<Self>),
+    /// The previous array element, repeated any number of times, written as `...`.
+    Variadic,
+}
+
+/// How an [`Arg`] is stored, in the form readable by earlier releases.
+///
+/// `...` is kept as a plain item, and optional elements are written as plain items too,
+/// since [`super::Syntax`] derives them from its params when read.
+/// The `optional:` form is still accepted.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ArgRepr {
+    Item(String),
+    Array(Vec<Self>),
+    Optional { optional: Box<Self> },
+}
+
+impl From<ArgRepr> for Arg {
+    fn from(repr: ArgRepr) -> Self {
+        match repr {
+            ArgRepr::Item(name) if name == "..." => Self::Variadic,
+            ArgRepr::Item(name) => Self::Item(name),
+            ArgRepr::Array(args) => Self::Array(args.into_iter().map(Self::from).collect()),
+            ArgRepr::Optional { optional } => Self::Optional(Box::new(Self::from(*optional))),
+        }
+    }
+}
+
+impl From<Arg> for ArgRepr {
+    fn from(arg: Arg) -> Self {
+        match arg {
+            Arg::Item(name) => Self::Item(name),
+            Arg::Array(args) => Self::Array(args.into_iter().map(Self::from).collect()),
+            Arg::Optional(arg) => Self::from(*arg),
+            Arg::Variadic => Self::Item("...".to_string()),
+        }
+    }
 }
 
 impl Arg {
@@ -12,6 +51,51 @@ impl Arg {
         match self {
             Self::Item(name) => vec![name.clone()],
             Self::Array(args) => args.iter().flat_map(Self::names).collect(),
+            Self::Optional(arg) => arg.names(),
+            Self::Variadic => Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// The least and most number of elements an array argument takes, `None` for no limit.
+    ///
+    /// Returns `None` if the argument is not an array.
+    pub fn arity(&self) -> Option<(usize, Option<usize>)> {
+        match self {
+            Self::Array(args) => {
+                let min = args
+                    .iter()
+                    .rposition(|arg| !matches!(arg, Self::Optional(_) | Self::Variadic))
+                    .map_or(0, |last| last + 1);
+                let max = (!args.contains(&Self::Variadic)).then_some(args.len());
+                Some((min, max))
+            }
+            Self::Optional(arg) => arg.arity(),
+            Self::Item(_) | Self::Variadic => None,
+        }
+    }
+
+    #[must_use]
+    /// Marks the array elements whose params are optional, and arrays whose elements all are.
+    pub fn with_optional(self, optional: &impl Fn(&str) -> bool) -> Self {
+        match self {
+            Self::Array(args) => Self::Array(
+                args.into_iter()
+                    .map(|arg| match arg.with_optional(optional) {
+                        Self::Item(name) if optional(&name) => {
+                            Self::Optional(Box::new(Self::Item(name)))
+                        }
+                        Self::Array(args)
+                            if !args.is_empty()
+                                && args.iter().all(|arg| matches!(arg, Self::Optional(_))) =>
+                        {
+                            Self::Optional(Box::new(Self::Array(args)))
+                        }
+                        arg => arg,
+                    })
+                    .collect(),
+            ),
+            arg => arg,
         }
     }
 }
@@ -95,6 +179,9 @@ impl Call {
         if item.is_empty() {
             return None;
         }
+        if item == "..." {
+            return Some(Arg::Variadic);
+        }
         Some(Arg::Item(item.to_owned()))
     }
 
@@ -142,6 +229,18 @@ impl Call {
         }
     }
 
+    #[must_use]
+    /// Marks the array elements whose params are optional, see [`Arg::with_optional`].
+    pub fn with_optional(self, optional: &impl Fn(&str) -> bool) -> Self {
+        match self {
+            Self::Nular => Self::Nular,
+            Self::Unary(arg) => Self::Unary(arg.with_optional(optional)),
+            Self::Binary(left, right) => {
+                Self::Binary(left.with_optional(optional), right.with_optional(optional))
+            }
+        }
+    }
+
     #[must_use]
     pub const fn is_nular(&self) -> bool {
         matches!(self, Self::Nular)
@@ -191,6 +290,53 @@ fn parse() {
     );
 }
 
+#[test]
+fn arity() {
+    let item = |name: &str| Arg::Item(name.to_string());
+    let optional = |name: &str| name == "name" || name == "row" || name == "column";
+    let arg = Call::parse_params("[idc, path, name]")
+        .unwrap()
+        .with_optional(&optional);
+    assert_eq!(
+        arg,
+        Arg::Array(vec![
+            item("idc"),
+            item("path"),
+            Arg::Optional(Box::new(item("name")))
+        ])
+    );
+    assert_eq!(arg.arity(), Some((2, Some(3))));
+    let arg = Call::parse_params("[idc, [row, column]]")
+        .unwrap()
+        .with_optional(&optional);
+    assert!(matches!(&arg, Arg::Array(args) if matches!(args[1], Arg::Optional(_))));
+    assert_eq!(arg.arity(), Some((1, Some(2))));
+
+    let arg = Call::parse_params("[argument1, argument2, ...]").unwrap();
+    assert_eq!(
+        arg,
+        Arg::Array(vec![item("argument1"), item("argument2"), Arg::Variadic])
+    );
+    assert_eq!(arg.arity(), Some((2, None)));
+    assert_eq!(arg.names(), ["argument1", "argument2"]);
+    assert_eq!(item("idc").arity(), None);
+}
+
+#[test]
+fn serialize() {
+    let arg = Arg::Array(vec![
+        Arg::Item("idc".to_string()),
+        Arg::Optional(Box::new(Arg::Item("name".to_string()))),
+        Arg::Variadic,
+    ]);
+    let yaml = serde_yaml::to_string(&arg).unwrap();
+    assert_eq!(yaml, "- idc\n- name\n- '...'\n");
+    assert_eq!(
+        serde_yaml::from_str::<Arg>("- idc\n- optional: name\n- '...'\n").unwrap(),
+        arg
+    );
+}
+
 #[test]
 #[cfg(feature = "wiki")]
 fn test_call_from_wiki() {
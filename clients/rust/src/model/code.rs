@@ -0,0 +1,242 @@
+#[cfg(feature = "wiki")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "wiki")]
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "wiki")]
+use super::wiki;
+use super::{ArraySizedElement, Value};
+
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+/// How a [`Value::CodeWithSignature`] is called, and what it is expected to return.
+pub struct CodeSignature {
+    /// The elements of the `_this` array passed to the code, in order.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) this: Vec<ArraySizedElement>,
+    /// The other variables available inside the code, such as `_x` or `_target`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) variables: Vec<ArraySizedElement>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ret: Option<Value>,
+}
+
+#[cfg(feature = "wiki")]
+static REGEX_MAGIC_VARIABLE: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_RETURN: OnceLock<Regex> = OnceLock::new();
+
+impl CodeSignature {
+    #[must_use]
+    pub const fn new(
+        this: Vec<ArraySizedElement>,
+        variables: Vec<ArraySizedElement>,
+        ret: Option<Value>,
+    ) -> Self {
+        Self {
+            this,
+            variables,
+            ret,
+        }
+    }
+
+    #[must_use]
+    pub fn this(&self) -> &[ArraySizedElement] {
+        &self.this
+    }
+
+    #[must_use]
+    pub fn variables(&self) -> &[ArraySizedElement] {
+        &self.variables
+    }
+
+    #[must_use]
+    pub const fn ret(&self) -> Option<&Value> {
+        self.ret.as_ref()
+    }
+
+    #[must_use]
+    /// The type of a variable inside the code, including `_this`.
+    pub fn variable(&self, name: &str) -> Option<Value> {
+        if name.eq_ignore_ascii_case("_this") && !self.this.is_empty() {
+            return Some(Value::ArraySized {
+                types: self.this.clone(),
+                desc: String::new(),
+            });
+        }
+        self.variables
+            .iter()
+            .find(|variable| variable.name.eq_ignore_ascii_case(name))
+            .map(|variable| variable.value.clone())
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Splits the documented signature off a code param, returning the rest of the param.
+    ///
+    /// The param must take `[[Code]]`, and be followed by a bullet for each element of `_this`,
+    /// written as `* name: [[Type]] - description`, or for each variable, written as `* _name: description`.
+    ///
+    /// # Panics
+    /// Panics if the regex fails to compile.
+    pub fn split_from_wiki(source: &str) -> Option<(String, Self)> {
+        let regex_magic_variable = REGEX_MAGIC_VARIABLE
+            .get_or_init(|| Regex::new(r"\{\{hl\|\[\[[^\]|]*\|(_\w+)\]\]\}\}").unwrap());
+        let regex_return = REGEX_RETURN.get_or_init(|| {
+            Regex::new(r"(?i)\b(?:must|should) return (?:an? )?\[\[(\w+)\]\]").unwrap()
+        });
+        let start = source.find("\n*")?;
+        let head = source[..start]
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("<sqf>"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !head.contains("[[Code]]") {
+            return None;
+        }
+        let mut signature = Self::default();
+        for (line, details) in wiki::bullets(&source[start..]) {
+            let line = regex_magic_variable.replace_all(line, "$1");
+            let (since, line) = wiki::strip_since(&line);
+            let (name, rest) = line.split_once(':')?;
+            let name = name.trim().trim_matches('\'');
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return None;
+            }
+            let element = if rest.contains("[[") {
+                // the version was stripped above, so `sized_element` can't see it
+                ArraySizedElement {
+                    since,
+                    ..Value::sized_element(&line, &details)
+                }
+            } else {
+                ArraySizedElement {
+                    name: name.to_string(),
+                    value: Value::Unknown,
+                    desc: rest.trim().to_string(),
+                    since,
+                    optional: false,
+                    default: None,
+                    string_kind: None,
+                }
+            };
+            if element.name.starts_with('_') {
+                signature.variables.push(element);
+            } else {
+                signature.this.push(element);
+            }
+        }
+        signature.ret = regex_return
+            .captures(&head)
+            .and_then(|caps| match &caps[1] {
+                "true" | "false" => Some(Value::Boolean),
+                typ => Value::single_match(typ).ok(),
+            });
+        Some((head, signature))
+    }
+}
+
+impl std::fmt::Display for CodeSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, element) in self.this.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", element.name, element.value)?;
+        }
+        write!(f, "]")?;
+        if let Some(ret) = &self.ret {
+            write!(f, " -> {ret}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "wiki")]
+mod tests {
+    use super::CodeSignature;
+    use crate::model::{Game, Value, Version};
+
+    #[test]
+    fn split_from_wiki() {
+        let (rest, script) = CodeSignature::split_from_wiki(r#"'''script''': [[String]] or [[Code]] - either path to the script file or the actual script code. Parameters array passed to the script upon activation in ''[[Magic Variables#this|_this]]'' variable is:
+<sqf>params ["_target", "_caller", "_actionId", "_arguments"];</sqf>
+* '''target''': [[Object]] - the object which the action is assigned to
+* '''caller''': [[Object]] - the unit that activated the action
+* '''actionID''': [[Number]] - activated action's ID (same as [[addAction]]'s return value)
+* '''arguments''': [[Anything]] - arguments given to the script if you are using the extended syntax
+* {{GVI|arma3|2.04}} '''source''': [[Object]] - the object the action came from"#).unwrap();
+        assert!(rest.starts_with("'''script''': [[String]] or [[Code]] - either"));
+        assert!(!rest.contains("params"));
+        assert_eq!(
+            script
+                .this()
+                .iter()
+                .map(|e| (e.name.as_str(), &e.value))
+                .collect::<Vec<_>>(),
+            [
+                ("target", &Value::Object),
+                ("caller", &Value::Object),
+                ("actionID", &Value::Number),
+                ("arguments", &Value::Anything),
+                ("source", &Value::Object)
+            ]
+        );
+        assert!(script.this()[3].since.is_none());
+        assert_eq!(
+            script.this()[4]
+                .since
+                .as_ref()
+                .and_then(|since| since.version(Game::Arma3)),
+            Some(&Version::new(2, 4))
+        );
+        assert!(script.variables().is_empty());
+        assert_eq!(script.ret(), None);
+        let Some(Value::ArraySized { types, .. }) = script.variable("_this") else {
+            panic!("expected a sized array");
+        };
+        assert_eq!(types[1].value, Value::Object);
+        assert_eq!(
+            script.to_string(),
+            "[target: Object, caller: Object, actionID: Number, arguments: Anything, source: Object]"
+        );
+
+        let (_, code) = CodeSignature::split_from_wiki(
+            "code: [[Code]] - code applied to each key-value pair - available variables:
+* {{hl|[[Magic Variables#x|_x]]}}: key
+* {{hl|[[Magic Variables#x|_y]]}}: value
+* {{GVI|arma3|2.04|size= 0.75}} {{hl|[[Magic Variables#forEachIndex|_forEachIndex]]}}: [[Number]] - iteration number",
+        )
+        .unwrap();
+        assert!(code.this().is_empty());
+        assert_eq!(code.variables()[1].name, "_y");
+        assert_eq!(code.variables()[1].desc, "value");
+        assert_eq!(code.variables()[2].value, Value::Number);
+        assert!(code.variables()[2].since.is_some());
+        assert_eq!(code.variable("_x"), Some(Value::Unknown));
+
+        let (_, condition) = CodeSignature::split_from_wiki(
+            "condition: [[Code]] - code that must return [[true]] for the trigger to activate
+* ''_target'': [[Object]] - the object",
+        )
+        .unwrap();
+        assert_eq!(condition.ret(), Some(&Value::Boolean));
+        assert_eq!(condition.variable("_target"), Some(Value::Object));
+
+        assert!(
+            CodeSignature::split_from_wiki(
+                "sound: [[String]] or [[Array]]\n* [[String]] - classname"
+            )
+            .is_none()
+        );
+        assert!(
+            CodeSignature::split_from_wiki("code: [[Code]] - one of:\n* [[String]] - a string")
+                .is_none()
+        );
+    }
+}
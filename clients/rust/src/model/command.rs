@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use super::{Locality, ParseError, Since, Syntax};
+#[cfg(feature = "wiki")]
+use super::ParseError;
+use super::{Game, Locality, Resolution, Since, Syntax, Value, Version, resolve};
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Command {
@@ -34,6 +36,26 @@ pub struct Command {
 }
 
 impl Command {
+    #[must_use]
+    /// The command as documented in a version of a game, or `None` if it is newer.
+    pub fn at_version(&self, game: Game, version: &Version) -> Option<Self> {
+        if !self.since.is_available_in(game, version) {
+            return None;
+        }
+        let syntax = self
+            .syntax
+            .iter()
+            .filter_map(|syntax| syntax.at_version(game, version))
+            .collect::<Vec<_>>();
+        if syntax.is_empty() && !self.syntax.is_empty() {
+            return None;
+        }
+        Some(Self {
+            syntax,
+            ..self.clone()
+        })
+    }
+
     #[must_use]
     pub fn name(&self) -> &str {
         &self.name
@@ -173,6 +195,15 @@ impl Command {
         self.examples.push(example);
     }
 
+    #[must_use]
+    /// Finds the syntaxes matching a call site with the given operand types.
+    ///
+    /// Pass `None` for a missing operand, so a unary call only has `rhs`.
+    /// Syntaxes that do not match are returned with the reason they were rejected.
+    pub fn resolve(&self, lhs: Option<&Value>, rhs: Option<&Value>) -> Resolution<'_> {
+        resolve::resolve(&self.syntax, lhs, rhs)
+    }
+
     #[allow(clippy::too_many_lines)]
     #[cfg(feature = "wiki")]
     /// Parses a command from the wiki.
@@ -328,6 +359,7 @@ impl Command {
         Ok((command, errors))
     }
 
+    #[cfg(feature = "wiki")]
     fn get_cmd_name(name: &str) -> &str {
         match name {
             "!_a" => "!",
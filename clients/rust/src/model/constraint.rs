@@ -0,0 +1,260 @@
+#[cfg(feature = "wiki")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "wiki")]
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    Meters,
+    Seconds,
+    Degrees,
+    Percent,
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Meters => write!(f, "m"),
+            Self::Seconds => write!(f, "s"),
+            Self::Degrees => write!(f, "°"),
+            Self::Percent => write!(f, "%"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+/// The documented limits of a [`super::Value::Number`].
+pub struct NumberConstraint {
+    #[serde(default, deserialize_with = "finite")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) min: Option<f64>,
+    #[serde(default, deserialize_with = "finite")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max: Option<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) integer: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) unit: Option<Unit>,
+}
+
+// The bounds are never NaN, as `new`, `from_wiki` and deserializing all reject non-finite bounds.
+impl Eq for NumberConstraint {}
+
+/// Deserializes a bound, rejecting NaN and infinity.
+fn finite<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    let bound = Option::<f64>::deserialize(deserializer)?;
+    if bound.is_some_and(|bound| !bound.is_finite()) {
+        return Err(D::Error::custom("number constraint bounds must be finite"));
+    }
+    Ok(bound)
+}
+
+#[cfg(feature = "wiki")]
+static REGEX_RANGE: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_MIN: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_MAX: OnceLock<Regex> = OnceLock::new();
+
+impl NumberConstraint {
+    #[must_use]
+    /// Creates a constraint, or returns `None` if a bound is not finite.
+    pub fn new(
+        min: Option<f64>,
+        max: Option<f64>,
+        integer: bool,
+        unit: Option<Unit>,
+    ) -> Option<Self> {
+        (min.is_none_or(f64::is_finite) && max.is_none_or(f64::is_finite)).then_some(Self {
+            min,
+            max,
+            integer,
+            unit,
+        })
+    }
+
+    #[must_use]
+    pub const fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    #[must_use]
+    pub const fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    #[must_use]
+    pub const fn integer(&self) -> bool {
+        self.integer
+    }
+
+    #[must_use]
+    pub const fn unit(&self) -> Option<Unit> {
+        self.unit
+    }
+
+    #[must_use]
+    /// Whether a number satisfies the constraint.
+    pub fn allows(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min)
+            && self.max.is_none_or(|max| value <= max)
+            && (!self.integer || value.fract() == 0.0)
+    }
+
+    #[cfg(feature = "wiki")]
+    #[must_use]
+    /// Extracts the constraints from the description of a number, such as
+    /// `range 0..1`, `between 0 and 100`, `at least 1`, `integer` or `in meters`.
+    ///
+    /// # Panics
+    /// Panics if the regex fails to compile.
+    pub fn from_wiki(source: &str) -> Option<Self> {
+        const NUMBER: &str = r"(-?\d+(?:\.\d+)?)";
+        let regex_range = REGEX_RANGE.get_or_init(|| {
+            Regex::new(&format!(
+                r"(?i){NUMBER}\s*\.\.\s*{NUMBER}|\b(?:range|between|from)\s+(?:of\s+)?{NUMBER}\s*(?:-|to|and)\s*{NUMBER}|(?:^|\s-\s){NUMBER}\s*-\s*{NUMBER}\b"
+            ))
+            .unwrap()
+        });
+        let regex_min = REGEX_MIN.get_or_init(|| {
+            Regex::new(&format!(
+                r"(?i)\b(?:at least|minimum(?: of)?|min\.?)\s+{NUMBER}"
+            ))
+            .unwrap()
+        });
+        let regex_max = REGEX_MAX.get_or_init(|| {
+            Regex::new(&format!(
+                r"(?i)\b(?:at most|maximum(?: of)?|max\.?)\s+{NUMBER}"
+            ))
+            .unwrap()
+        });
+        let parse = |m: Option<regex::Match>| {
+            m.and_then(|m| m.as_str().parse::<f64>().ok())
+                .filter(|bound| bound.is_finite())
+        };
+
+        let mut constraint = Self::default();
+        if let Some(caps) = regex_range.captures(source) {
+            let mut bounds = caps.iter().skip(1).flatten();
+            constraint.min = parse(bounds.next());
+            constraint.max = parse(bounds.next());
+        }
+        if let Some(caps) = regex_min.captures(source) {
+            constraint.min = parse(caps.get(1));
+        }
+        if let Some(caps) = regex_max.captures(source) {
+            constraint.max = parse(caps.get(1));
+        }
+        let lower = source.to_lowercase();
+        let has_word = |words: &[&str]| {
+            lower
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| words.contains(&word))
+        };
+        constraint.integer = has_word(&["integer", "integers"]) || lower.contains("whole number");
+        constraint.unit = if has_word(&["meters", "metres", "meter", "metre"]) {
+            Some(Unit::Meters)
+        } else if has_word(&["seconds", "sec"]) {
+            Some(Unit::Seconds)
+        } else if has_word(&["degrees", "degree"]) || lower.contains('°') {
+            Some(Unit::Degrees)
+        } else if has_word(&["percent", "percentage"]) || lower.contains('%') {
+            Some(Unit::Percent)
+        } else {
+            None
+        };
+        (constraint != Self::default()).then_some(constraint)
+    }
+}
+
+impl std::fmt::Display for NumberConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => parts.push(format!("{min}..{max}")),
+            (Some(min), None) => parts.push(format!(">= {min}")),
+            (None, Some(max)) => parts.push(format!("<= {max}")),
+            (None, None) => {}
+        }
+        if self.integer {
+            parts.push("integer".to_string());
+        }
+        if let Some(unit) = self.unit {
+            parts.push(format!("in {unit}"));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "wiki")]
+mod tests {
+    use super::{NumberConstraint, Unit};
+
+    #[test]
+    fn from_wiki() {
+        assert_eq!(
+            NumberConstraint::from_wiki(
+                "range 0..1, 0 = pristine/healthy condition, 1 = entirely destroyed/killed"
+            ),
+            NumberConstraint::new(Some(0.0), Some(1.0), false, None)
+        );
+        assert_eq!(
+            NumberConstraint::from_wiki("transition time in seconds to the new value"),
+            NumberConstraint::new(None, None, false, Some(Unit::Seconds))
+        );
+        assert_eq!(
+            NumberConstraint::from_wiki("volume, between 0 and 100 percent"),
+            NumberConstraint::new(Some(0.0), Some(100.0), false, Some(Unit::Percent))
+        );
+        assert_eq!(
+            NumberConstraint::from_wiki("0-360 degrees"),
+            NumberConstraint::new(Some(0.0), Some(360.0), false, Some(Unit::Degrees))
+        );
+        assert_eq!(
+            NumberConstraint::from_wiki("integer index, at least 1"),
+            NumberConstraint::new(Some(1.0), None, true, None)
+        );
+        assert_eq!(
+            NumberConstraint::from_wiki("(Optional, default -1) size of the text in UI units"),
+            None
+        );
+        assert_eq!(NumberConstraint::from_wiki("the second element"), None);
+        assert_eq!(
+            NumberConstraint::from_wiki(&format!("at least 1{}", "0".repeat(400))),
+            None
+        );
+    }
+
+    #[test]
+    fn finite() {
+        assert_eq!(
+            NumberConstraint::new(Some(f64::NAN), None, false, None),
+            None
+        );
+        assert_eq!(
+            NumberConstraint::new(None, Some(f64::INFINITY), false, None),
+            None
+        );
+        assert!(serde_yaml::from_str::<NumberConstraint>("min: .nan").is_err());
+        assert_eq!(
+            serde_yaml::from_str::<NumberConstraint>("min: 0.0\nmax: 1.0").ok(),
+            NumberConstraint::new(Some(0.0), Some(1.0), false, None)
+        );
+    }
+
+    #[test]
+    fn allows() {
+        let damage = NumberConstraint::new(Some(0.0), Some(1.0), false, None).unwrap();
+        assert!(damage.allows(0.5));
+        assert!(!damage.allows(5.0));
+        let index = NumberConstraint::new(Some(0.0), None, true, None).unwrap();
+        assert!(index.allows(3.0));
+        assert!(!index.allows(1.5));
+        assert_eq!(damage.to_string(), "0..1");
+    }
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use super::{Locality, Param, Since};
+use super::{Game, Locality, Param, Since, Version};
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum EventHandler {
@@ -35,6 +35,26 @@ pub struct ParsedEventHandler {
 }
 
 impl ParsedEventHandler {
+    #[must_use]
+    /// The event handler as documented in a version of a game, or `None` if it is newer.
+    pub fn at_version(&self, game: Game, version: &Version) -> Option<Self> {
+        if self
+            .since
+            .as_ref()
+            .is_some_and(|since| !since.is_available_in(game, version))
+        {
+            return None;
+        }
+        Some(Self {
+            params: self
+                .params
+                .iter()
+                .filter_map(|p| p.at_version(game, version))
+                .collect(),
+            ..self.clone()
+        })
+    }
+
     #[must_use]
     pub fn id(&self) -> &str {
         &self.id
@@ -232,7 +252,9 @@ impl ParsedEventHandler {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
 #[allow(clippy::module_name_repetitions)]
 pub enum EventHandlerNamespace {
     Standard,
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// A game documented by the wiki, in release order.
+pub enum Game {
+    Flashpoint,
+    FlashpointElite,
+    ArmedAssault,
+    Arma2,
+    Arma2Arrowhead,
+    TakeOnHelicopters,
+    Arma3,
+    Argo,
+}
+
+impl Game {
+    /// Every game, in release order.
+    pub const ALL: [Self; 8] = [
+        Self::Flashpoint,
+        Self::FlashpointElite,
+        Self::ArmedAssault,
+        Self::Arma2,
+        Self::Arma2Arrowhead,
+        Self::TakeOnHelicopters,
+        Self::Arma3,
+        Self::Argo,
+    ];
+
+    pub fn iter() -> std::slice::Iter<'static, Self> {
+        Self::ALL.iter()
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Flashpoint => "Operation Flashpoint",
+            Self::FlashpointElite => "Operation Flashpoint: Elite",
+            Self::ArmedAssault => "ArmA: Armed Assault",
+            Self::Arma2 => "Arma 2",
+            Self::Arma2Arrowhead => "Arma 2: Operation Arrowhead",
+            Self::TakeOnHelicopters => "Take On Helicopters",
+            Self::Arma3 => "Arma 3",
+            Self::Argo => "Argo",
+        }
+    }
+
+    #[must_use]
+    /// The key used by the wiki's `{{GVI}}` template, such as `arma2oa`.
+    pub const fn wiki_key(&self) -> &'static str {
+        match self {
+            Self::Flashpoint => "ofp",
+            Self::FlashpointElite => "ofpe",
+            Self::ArmedAssault => "arma1",
+            Self::Arma2 => "arma2",
+            Self::Arma2Arrowhead => "arma2oa",
+            Self::TakeOnHelicopters => "tkoh",
+            Self::Arma3 => "arma3",
+            Self::Argo => "argo",
+        }
+    }
+
+    #[must_use]
+    pub fn from_wiki_key(key: &str) -> Option<Self> {
+        Self::iter()
+            .find(|game| game.wiki_key().eq_ignore_ascii_case(key))
+            .copied()
+    }
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
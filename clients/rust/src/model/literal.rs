@@ -0,0 +1,224 @@
+use super::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+/// A literal SQF value, such as the default of an optional param.
+pub enum Literal {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Array(Vec<Self>),
+    /// A nular command used as a value, such as `objNull` or `west`.
+    Nular(String),
+}
+
+impl Literal {
+    #[must_use]
+    /// Parses a literal from wiki text, such as `[[objNull]]`, `"NONE"` or `["", [0,0,-1]]`.
+    ///
+    /// Returns `None` if the text is not a literal, such as a description of the default.
+    pub fn parse(source: &str) -> Option<Self> {
+        let source = source.trim();
+        let source = source
+            .strip_prefix("{{hl|")
+            .and_then(|s| s.strip_suffix("}}"))
+            .unwrap_or(source)
+            .trim();
+        if let Some(link) = source.strip_prefix("[[").and_then(|s| s.strip_suffix("]]")) {
+            // `[[Side#west|west]]` displays `west`
+            return Self::parse(link.rsplit('|').next().unwrap_or(link));
+        }
+        if let Some(array) = source.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            return split_elements(array)?
+                .into_iter()
+                .map(Self::parse)
+                .collect::<Option<Vec<_>>>()
+                .map(Self::Array);
+        }
+        for quote in ['"', '\''] {
+            if source.len() >= 2
+                && let Some(string) = source
+                    .strip_prefix(quote)
+                    .and_then(|s| s.strip_suffix(quote))
+            {
+                return Some(Self::String(string.to_string()));
+            }
+        }
+        if source.eq_ignore_ascii_case("true") {
+            return Some(Self::Boolean(true));
+        }
+        if source.eq_ignore_ascii_case("false") {
+            return Some(Self::Boolean(false));
+        }
+        if let Ok(number) = source.parse::<f64>()
+            && number.is_finite()
+        {
+            return Some(Self::Number(number));
+        }
+        let mut chars = source.chars();
+        if chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Some(Self::Nular(source.to_string()));
+        }
+        None
+    }
+
+    #[must_use]
+    /// The type of the literal, if it is known.
+    ///
+    /// Only the null values and sides are known among nular commands.
+    pub fn typ(&self) -> Option<Value> {
+        Some(match self {
+            Self::Number(_) => Value::Number,
+            Self::String(_) => Value::String,
+            Self::Boolean(_) => Value::Boolean,
+            Self::Array(_) => Value::ArrayUnknown,
+            Self::Nular(name) => match name.to_lowercase().as_str() {
+                "objnull" | "player" => Value::Object,
+                "grpnull" => Value::Group,
+                "controlnull" => Value::Control,
+                "displaynull" => Value::Display,
+                "locationnull" => Value::Location,
+                "tasknull" => Value::Task,
+                "scriptnull" => Value::ScriptHandle,
+                "teammembernull" => Value::TeamMember,
+                "confignull" => Value::Config,
+                "diaryrecordnull" => Value::DiaryRecord,
+                "west" | "blufor" | "east" | "opfor" | "independent" | "resistance"
+                | "civilian" | "sidelogic" | "sideunknown" | "sideenemy" | "sidefriendly"
+                | "sideempty" | "sideambientlife" => Value::Side,
+                _ => return None,
+            },
+        })
+    }
+
+    #[must_use]
+    /// Whether the literal can be passed where `expected` is expected.
+    ///
+    /// Literals of unknown type, and `nil`, are always accepted.
+    pub fn is_valid_for(&self, expected: &Value) -> bool {
+        match (self, expected) {
+            (Self::Nular(name), _) if name.eq_ignore_ascii_case("nil") => true,
+            (_, Value::OneOf(alternatives)) => alternatives
+                .iter()
+                .any(|(alternative, _)| self.is_valid_for(alternative)),
+            (Self::Array(elements), Value::ArraySized { types, .. }) => {
+                elements.len() <= types.len()
+                    && elements
+                        .iter()
+                        .zip(types)
+                        .all(|(element, typ)| element.is_valid_for(&typ.value))
+            }
+            (Self::Array(elements), Value::ArrayUnsized { typ, .. }) => {
+                elements.iter().all(|element| element.is_valid_for(typ))
+            }
+            (Self::Array(_), _) => expected.is_assignable_to(&Value::ArrayUnknown),
+            _ => self.typ().is_none_or(|typ| typ.is_assignable_to(expected)),
+        }
+    }
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::String(string) => write!(f, "\"{}\"", string.replace('"', "\"\"")),
+            Self::Boolean(boolean) => write!(f, "{boolean}"),
+            Self::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Nular(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Splits the inside of an array literal at its top level commas.
+fn split_elements(source: &str) -> Option<Vec<&str>> {
+    let mut elements = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in source.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('[', None) => depth += 1,
+            (']', None) => depth = depth.checked_sub(1)?,
+            (',', None) if depth == 0 => {
+                elements.push(&source[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || quote.is_some() {
+        return None;
+    }
+    if !source[start..].trim().is_empty() || !elements.is_empty() {
+        elements.push(&source[start..]);
+    }
+    Some(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Literal;
+    use crate::model::Value;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Literal::parse("[[false]]"), Some(Literal::Boolean(false)));
+        assert_eq!(
+            Literal::parse("[[objNull]]"),
+            Some(Literal::Nular("objNull".to_string()))
+        );
+        assert_eq!(Literal::parse("-1"), Some(Literal::Number(-1.0)));
+        assert_eq!(
+            Literal::parse("{{hl|\"NONE\"}}"),
+            Some(Literal::String("NONE".to_string()))
+        );
+        assert_eq!(Literal::parse("\"\""), Some(Literal::String(String::new())));
+        assert_eq!(
+            Literal::parse("[\"\", [0,0,-1]]"),
+            Some(Literal::Array(vec![
+                Literal::String(String::new()),
+                Literal::Array(vec![
+                    Literal::Number(0.0),
+                    Literal::Number(0.0),
+                    Literal::Number(-1.0)
+                ])
+            ]))
+        );
+        assert_eq!(Literal::parse("[]"), Some(Literal::Array(Vec::new())));
+        assert_eq!(Literal::parse("current time"), None);
+        assert_eq!(
+            Literal::parse("[\"\", [0,0,-1]]").unwrap().to_string(),
+            "[\"\", [0, 0, -1]]"
+        );
+    }
+
+    #[test]
+    fn valid_for() {
+        assert!(Literal::Number(0.0).is_valid_for(&Value::Number));
+        assert!(!Literal::Number(0.0).is_valid_for(&Value::String));
+        assert!(Literal::Nular("objNull".to_string()).is_valid_for(&Value::Object));
+        assert!(!Literal::Nular("objNull".to_string()).is_valid_for(&Value::Group));
+        assert!(Literal::Nular("nil".to_string()).is_valid_for(&Value::Group));
+        assert!(Literal::Nular("someCommand".to_string()).is_valid_for(&Value::Group));
+        assert!(Literal::Boolean(false).is_valid_for(&Value::OneOf(vec![
+            (Value::Number, None),
+            (Value::Boolean, None)
+        ])));
+        assert!(Literal::Array(Vec::new()).is_valid_for(&Value::Position));
+        assert!(!Literal::Array(Vec::new()).is_valid_for(&Value::Number));
+    }
+}
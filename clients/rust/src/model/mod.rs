@@ -1,19 +1,35 @@
+mod binding;
 mod call;
+mod code;
 mod command;
+mod constraint;
 mod event_handler;
+mod game;
+mod literal;
 mod locality;
 mod param;
+mod resolve;
 mod since;
+mod string_kind;
 mod syntax;
 mod value;
 mod version;
+#[cfg(feature = "wiki")]
+mod wiki;
 
+pub use binding::{Binding, Bindings};
 pub use call::{Arg, Call};
+pub use code::CodeSignature;
 pub use command::Command;
+pub use constraint::{NumberConstraint, Unit};
 pub use event_handler::{EventHandler, EventHandlerNamespace, ParsedEventHandler};
+pub use game::Game;
+pub use literal::Literal;
 pub use locality::Locality;
-pub use param::Param;
+pub use param::{EnumValue, Param};
+pub use resolve::{MatchQuality, Operand, Rejection, Resolution};
 pub use since::Since;
+pub use string_kind::StringKind;
 pub use syntax::Syntax;
 pub use value::{ArraySizedElement, Value};
 pub use version::Version;
@@ -22,6 +38,7 @@ pub use version::Version;
 pub enum ParseError {
     Syntax(String),
     UnknownType(String),
+    InvalidDefault(String),
 }
 
 impl std::fmt::Display for ParseError {
@@ -29,6 +46,7 @@ impl std::fmt::Display for ParseError {
         match self {
             Self::Syntax(s) => write!(f, "Syntax Error: {s}"),
             Self::UnknownType(s) => write!(f, "Unknown Type: `{s}`"),
+            Self::InvalidDefault(s) => write!(f, "Invalid Default: {s}"),
         }
     }
 }
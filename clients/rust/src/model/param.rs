@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::model::Version;
 
-use super::{ParseError, Since, Value};
+use super::{CodeSignature, Game, Literal, NumberConstraint, Since, StringKind, Value};
+#[cfg(feature = "wiki")]
+use super::{ParseError, wiki};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "ParamRepr", into = "ParamRepr")]
 pub struct Param {
     pub(crate) name: String,
     #[serde(default)]
@@ -21,6 +24,169 @@ pub struct Param {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) since: Option<Since>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) values: Vec<EnumValue>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) constraint: Option<NumberConstraint>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) string_kind: Option<StringKind>,
+}
+
+/// How a [`Param`] is stored, in the form readable by earlier releases.
+///
+/// A [`Value::CodeWithSignature`] is written as [`Value::Code`], with the signature alongside it.
+/// Reading a signature inside the type is still accepted.
+#[derive(Clone, Serialize, Deserialize)]
+struct ParamRepr {
+    name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(rename = "type")]
+    typ: Value,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<CodeSignature>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    optional: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<Since>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    values: Vec<EnumValue>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constraint: Option<NumberConstraint>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    string_kind: Option<StringKind>,
+}
+
+impl From<ParamRepr> for Param {
+    fn from(repr: ParamRepr) -> Self {
+        Self {
+            name: repr.name,
+            description: repr.description,
+            typ: match &repr.signature {
+                Some(signature) => with_signature(repr.typ, signature),
+                None => repr.typ,
+            },
+            optional: repr.optional,
+            default: repr.default,
+            since: repr.since,
+            values: repr.values,
+            constraint: repr.constraint,
+            string_kind: repr.string_kind,
+        }
+    }
+}
+
+impl From<Param> for ParamRepr {
+    fn from(param: Param) -> Self {
+        let mut signature = None;
+        let typ = without_signature(param.typ, &mut signature);
+        Self {
+            name: param.name,
+            description: param.description,
+            typ,
+            signature,
+            optional: param.optional,
+            default: param.default,
+            since: param.since,
+            values: param.values,
+            constraint: param.constraint,
+            string_kind: param.string_kind,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// A literal value accepted by a param.
+pub struct EnumValue {
+    pub(crate) value: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) since: Option<Since>,
+}
+
+impl EnumValue {
+    #[must_use]
+    pub const fn new(value: String, description: Option<String>, since: Option<Since>) -> Self {
+        Self {
+            value,
+            description,
+            since,
+        }
+    }
+
+    #[must_use]
+    /// The literal, without quotes.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    #[must_use]
+    pub const fn since(&self) -> Option<&Since> {
+        self.since.as_ref()
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Splits a `* {{hl|"VALUE"}} - description` bullet list off a string param.
+    ///
+    /// Returns the source without the list, or `None` if the param has no such list.
+    fn split_from_wiki(source: &str) -> Option<(String, Vec<Self>)> {
+        let (first, _) = source.split_once('\n')?;
+        if !first.contains("[[String]]") {
+            return None;
+        }
+        let mut values = Vec::new();
+        for (line, _) in wiki::bullets(source) {
+            let (since, line) = wiki::strip_since(line);
+            if !line.starts_with("{{hl|") {
+                return None;
+            }
+            let (literals, description) = line.split_once(" - ").unwrap_or((&line, ""));
+            let description = description.trim();
+            for literal in literals.split("{{hl|").skip(1) {
+                let (literal, _) = literal.split_once("}}")?;
+                let literal = literal.trim();
+                values.push(Self::new(
+                    literal
+                        .strip_prefix('"')
+                        .and_then(|l| l.strip_suffix('"'))
+                        .unwrap_or(literal)
+                        .to_string(),
+                    (!description.is_empty()).then(|| description.to_string()),
+                    since.clone(),
+                ));
+            }
+        }
+        if values.is_empty() {
+            return None;
+        }
+        let rest = source
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('*'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((rest, values))
+    }
 }
 
 impl Param {
@@ -40,9 +206,13 @@ impl Param {
             optional,
             default,
             since,
+            values: Vec::new(),
+            constraint: None,
+            string_kind: None,
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     #[cfg(feature = "wiki")]
     /// Parses a param from a wiki command.
     ///
@@ -68,6 +238,11 @@ impl Param {
         } else {
             value
         };
+        let (value, values) = EnumValue::split_from_wiki(&value).unwrap_or((value, Vec::new()));
+        let (value, signature) = match CodeSignature::split_from_wiki(&value) {
+            Some((value, signature)) => (value, Some(signature)),
+            None => (value, None),
+        };
         let (mut name, desc, typ) = if value.contains("\n*") {
             // multiple types
             let Some((mut name, types)) = value.split_once(':') else {
@@ -122,8 +297,9 @@ impl Param {
         } else {
             None
         };
-        Ok((
-            Self::new(
+        let mut param = Self {
+            values,
+            ..Self::new(
                 {
                     let mut name = name.to_string();
                     if name.starts_with("'''") {
@@ -146,9 +322,40 @@ impl Param {
                 optional,
                 default,
                 since,
-            ),
-            errors,
-        ))
+            )
+        };
+        if let Some(signature) = signature {
+            param.typ = with_signature(param.typ, &signature);
+        }
+        if param.typ == Value::Number {
+            param.constraint = NumberConstraint::from_wiki(&value);
+        }
+        param.string_kind = StringKind::from_wiki(&param.typ, &value);
+        if let Some(default) = param.default_value()
+            && !default.is_valid_for(&param.typ)
+        {
+            errors.push(ParseError::InvalidDefault(format!(
+                "{}: {default} is not {}",
+                param.name, param.typ
+            )));
+        }
+        Ok((param, errors))
+    }
+
+    #[must_use]
+    /// The param as documented in a version of a game, or `None` if it is newer.
+    pub fn at_version(&self, game: Game, version: &Version) -> Option<Self> {
+        if self
+            .since
+            .as_ref()
+            .is_some_and(|since| !since.is_available_in(game, version))
+        {
+            return None;
+        }
+        Some(Self {
+            typ: self.typ.at_version(game, version),
+            ..self.clone()
+        })
     }
 
     #[must_use]
@@ -172,10 +379,17 @@ impl Param {
     }
 
     #[must_use]
+    /// The default as written on the wiki.
     pub fn default(&self) -> Option<&str> {
         self.default.as_deref()
     }
 
+    #[must_use]
+    /// The default parsed into a literal, if it is one.
+    pub fn default_value(&self) -> Option<Literal> {
+        self.default.as_deref().and_then(Literal::parse)
+    }
+
     #[must_use]
     pub const fn since(&self) -> Option<&Since> {
         self.since.as_ref()
@@ -185,6 +399,24 @@ impl Param {
         self.since.get_or_insert_with(Since::default)
     }
 
+    #[must_use]
+    /// The documented limits of a number param.
+    pub const fn constraint(&self) -> Option<&NumberConstraint> {
+        self.constraint.as_ref()
+    }
+
+    #[must_use]
+    /// What a string param holds, such as a class name or a file path.
+    pub const fn string_kind(&self) -> Option<&StringKind> {
+        self.string_kind.as_ref()
+    }
+
+    #[must_use]
+    /// The literal values the param is limited to, if documented.
+    pub fn values(&self) -> &[EnumValue] {
+        &self.values
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name = name;
     }
@@ -204,14 +436,61 @@ impl Param {
     pub fn set_since(&mut self, since: Option<Since>) {
         self.since = since;
     }
+
+    pub fn set_values(&mut self, values: Vec<EnumValue>) {
+        self.values = values;
+    }
+
+    pub fn set_constraint(&mut self, constraint: Option<NumberConstraint>) {
+        self.constraint = constraint;
+    }
+
+    pub fn set_string_kind(&mut self, string_kind: Option<StringKind>) {
+        self.string_kind = string_kind;
+    }
+}
+
+/// Attaches a documented signature to the code in a type.
+///
+/// A type that could not be parsed is assumed to be the code the signature documents.
+fn with_signature(typ: Value, signature: &CodeSignature) -> Value {
+    match typ {
+        Value::Code | Value::Unknown => Value::CodeWithSignature(Box::new(signature.clone())),
+        Value::OneOf(alternatives) => Value::OneOf(
+            alternatives
+                .into_iter()
+                .map(|(typ, since)| (with_signature(typ, signature), since))
+                .collect(),
+        ),
+        typ => typ,
+    }
+}
+
+/// Replaces the signatures in a type with plain code, keeping the signature.
+fn without_signature(typ: Value, signature: &mut Option<CodeSignature>) -> Value {
+    match typ {
+        Value::CodeWithSignature(code) => {
+            *signature = Some(*code);
+            Value::Code
+        }
+        Value::OneOf(alternatives) => Value::OneOf(
+            alternatives
+                .into_iter()
+                .map(|(typ, since)| (without_signature(typ, signature), since))
+                .collect(),
+        ),
+        typ => typ,
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "wiki")]
 mod tests {
-    use crate::model::Value;
+    use crate::model::{
+        Game, Literal, NumberConstraint, ParseError, Since, StringKind, Value, Version,
+    };
 
-    use super::Param;
+    use super::{EnumValue, Param};
 
     #[test]
     fn simple() {
@@ -221,26 +500,142 @@ mod tests {
         assert_eq!(alive.typ(), &Value::Object);
     }
 
+    #[test]
+    fn default() {
+        let (public, errors) = Param::from_wiki(
+            "setVariable",
+            "public: [[Boolean]] - (Optional, default [[false]]) broadcast the variable",
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(public.default(), Some("[[false]]"));
+        assert_eq!(public.default_value(), Some(Literal::Boolean(false)));
+
+        let (target, errors) = Param::from_wiki(
+            "doWatch",
+            "target: [[Object]] - (Optional, default 0) target to watch",
+        )
+        .unwrap();
+        assert_eq!(target.default_value(), Some(Literal::Number(0.0)));
+        assert_eq!(
+            errors,
+            [ParseError::InvalidDefault(
+                "target: 0 is not Object".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn constraint() {
+        let (damage, _) = Param::from_wiki("setDamage", "damage: [[Number]] - range 0..1, 0 = pristine/healthy condition, 1 = entirely destroyed/killed").unwrap();
+        assert_eq!(
+            damage.constraint(),
+            NumberConstraint::new(Some(0.0), Some(1.0), false, None).as_ref()
+        );
+        let (name, _) = Param::from_wiki("setName", "name: [[String]] - range 0..1").unwrap();
+        assert_eq!(name.constraint(), None);
+    }
+
+    #[test]
+    fn code_signature() {
+        let (script, _) = Param::from_wiki("addAction", r#"'''script''': [[String]] or [[Code]] - either path to the script file, relative to the mission folder or string with code or the actual script code. Parameters array passed to the script upon activation in ''[[Magic Variables#this|_this]]'' variable is:
+<sqf>params ["_target", "_caller", "_actionId", "_arguments"];</sqf>
+* '''target''': [[Object]] - the object which the action is assigned to
+* '''caller''': [[Object]] - the unit that activated the action
+* '''actionID''': [[Number]] - activated action's ID (same as [[addAction]]'s return value)
+* '''arguments''': [[Anything]] - arguments given to the script if you are using the extended syntax"#).unwrap();
+        assert_eq!(script.name(), "script");
+        let Value::OneOf(alternatives) = script.typ() else {
+            panic!("expected alternatives");
+        };
+        assert_eq!(alternatives[0], (Value::String, None));
+        let Value::CodeWithSignature(signature) = &alternatives[1].0 else {
+            panic!("expected code with a signature");
+        };
+        assert_eq!(signature.this()[2].name, "actionID");
+        assert_eq!(signature.this()[2].value, Value::Number);
+
+        // the type is written as plain code, as earlier releases expect
+        let yaml = serde_yaml::to_string(&script).unwrap();
+        assert!(
+            yaml.contains("type: !OneOf\n- - String\n  - null\n- - Code\n  - null\nsignature:\n")
+        );
+        assert_eq!(serde_yaml::from_str::<Param>(&yaml).unwrap(), script);
+    }
+
     #[test]
     fn one_of() {
         let (direction, _) = Param::from_wiki("camSetDir", "direction:\n* [[Number]] (before {{GVI|arma3|0.50}}) - camera azimuth\n* [[Array]] in format [x,y,z] (since {{GVI|arma3|0.50}}) - direction of camera. Must be a valid vector.").unwrap();
         assert_eq!(direction.name(), "direction");
-        assert_eq!(direction.typ(), &Value::Unknown);
+        let mut since = Since::default();
+        since.set_arma_3(Some(Version::new(0, 50)));
+        let mut before = Since::default();
+        before.set_until(Some((Game::Arma3, Version::new(0, 50))));
+        assert_eq!(
+            direction.typ(),
+            &Value::OneOf(vec![
+                (Value::Number, Some(before)),
+                (Value::ArrayUnknown, Some(since))
+            ])
+        );
+        assert_eq!(
+            direction.typ().at_version(Game::Arma3, &Version::new(2, 0)),
+            Value::ArrayUnknown
+        );
+        assert_eq!(
+            direction
+                .typ()
+                .at_version(Game::Arma3, &Version::new(0, 40)),
+            Value::Number
+        );
 
         let (public, _) = Param::from_wiki("setVariable", "public - (Optional, default [[false]]) can be one of:\n* [[Boolean]] - if set to [[true]], the variable is broadcast globally and is persistent ([[Multiplayer Scripting#Join In Progress|JIP]] compatible) {{Icon|globalEffect|32}}\n* [[Number]] - the variable is only set on the client with the given [[Multiplayer Scripting#Machine network ID|Machine network ID]]. If the number is negative, the variable is set on every client except for the one with the given ID.\n* [[Array]] of [[Number]]s - array of [[Multiplayer Scripting#Machine network ID|Machine network IDs]]").unwrap();
         assert_eq!(public.name(), "public");
-        assert_eq!(public.typ(), &Value::Unknown);
+        assert_eq!(
+            public.typ(),
+            &Value::OneOf(vec![
+                (Value::Boolean, None),
+                (Value::Number, None),
+                (
+                    Value::ArrayUnsized {
+                        typ: Box::new(Value::Number),
+                        desc: String::new()
+                    },
+                    None
+                )
+            ])
+        );
 
         let (targets, _) = Param::from_wiki("remoteExec", "'''targets''' - (Optional, default 0):\n* [[Number]] (See also [[Multiplayer Scripting#Machine network ID|Machine network ID]]):\n** '''0:''' the order will be executed globally, i.e. on the server and every connected client, including the machine where [[remoteExec]] originated\n** '''2:''' the order will only be executed on the server - is both dedicated and hosted server. See [[Multiplayer_Scripting#Different_machines_and_how_to_target_them|for more info]]\n** '''Other number:''' the order will be executed on the machine where [[clientOwner]] matches the given number\n** '''Negative number:''' the effect is inverted: '''-2''' means every client but not the server, '''-12''' means the server and every client, except for the client where [[clientOwner]] returns 12\n* [[Object]] - the order will be executed where the given object is [[Multiplayer Scripting#Locality|local]]\n* [[String]] - interpreted as an [[Identifier]] (variable name); the function / command will be executed where the object or group identified by the variable with the provided name is [[Multiplayer Scripting#Locality|local]]\n* [[Side]] - the order will be executed on machines where the player is on the specified side\n* [[Group]] - the order will be executed on machines '''where the player is in the specified group''' ('''not''' where said group is local!)\n* [[Array]] - array of any combination of the types listed above").unwrap();
         assert_eq!(targets.name(), "targets");
-        assert_eq!(targets.typ(), &Value::Unknown);
+        assert_eq!(
+            targets.typ(),
+            &Value::OneOf(vec![
+                (Value::Number, None),
+                (Value::Object, None),
+                (Value::String, None),
+                (Value::Side, None),
+                (Value::Group, None),
+                (Value::ArrayUnknown, None)
+            ])
+        );
     }
 
     #[test]
     fn or() {
         let (targets, _) = Param::from_wiki("remoteExec", "'''targets''': [[Number]], [[Object]], [[String]], [[Side]], [[Group]] or [[Array]] - (Optional, default 0) see the main syntax above for more details.").unwrap();
         assert_eq!(targets.name(), "targets");
-        assert_eq!(targets.typ(), &Value::Unknown);
+        assert_eq!(
+            targets.typ(),
+            &Value::OneOf(vec![
+                (Value::Number, None),
+                (Value::Object, None),
+                (Value::String, None),
+                (Value::Side, None),
+                (Value::Group, None),
+                (Value::ArrayUnknown, None)
+            ])
+        );
     }
 
     #[test]
@@ -252,6 +647,25 @@ mod tests {
 If ''special'' is "" or not specified, default {{hl|"NONE"}} is used."#).unwrap();
         assert_eq!(special.name(), "special");
         assert!(special.optional());
+        assert_eq!(special.typ(), &Value::String);
+        assert_eq!(special.default(), Some("\"NONE\""));
+        assert_eq!(
+            special
+                .values()
+                .iter()
+                .map(EnumValue::value)
+                .collect::<Vec<_>>(),
+            ["NONE", "CAN_COLLIDE", "FLY"]
+        );
+        assert_eq!(
+            special.values()[2].description(),
+            Some(
+                "if vehicle is capable of flying and has crew, it will be made airborne at default height."
+            )
+        );
+        let yaml = serde_yaml::to_string(&special).unwrap();
+        assert!(yaml.contains("- value: CAN_COLLIDE"));
+        assert_eq!(serde_yaml::from_str::<Param>(&yaml).unwrap(), special);
 
         let (sound, _) = Param::from_wiki("say3D", r"sound: [[String]] or [[Array]]
 * [[String]] - classname of the sound to be played. Defined in [[CfgSounds]] including [[Description.ext]]
@@ -267,5 +681,41 @@ If ''special'' is "" or not specified, default {{hl|"NONE"}} is used."#).unwrap(
 ** {{GVI|arma3|2.18|size= 0.75}} simulateSpeedOfSound: [[Boolean]] - (Optional, default [[false]]) [[true]] to simulate speed of sound (see description note)").unwrap();
         assert_eq!(sound.name(), "sound");
         assert!(!sound.optional());
+        let Value::OneOf(alternatives) = sound.typ() else {
+            panic!("expected alternatives");
+        };
+        assert_eq!(alternatives[0], (Value::String, None));
+        let Value::ArraySized { types, .. } = &alternatives[1].0 else {
+            panic!("expected a sized array");
+        };
+        assert_eq!(
+            types.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            [
+                "sound",
+                "maxDistance",
+                "pitch",
+                "isSpeech",
+                "offset",
+                "simulateSpeedOfSound"
+            ]
+        );
+        assert_eq!(types[1].default.as_deref(), Some("100"));
+        let cfg_sounds = StringKind::ClassName(Some("CfgSounds".to_string()));
+        assert_eq!(sound.string_kind(), Some(&cfg_sounds));
+        assert_eq!(types[0].string_kind, Some(cfg_sounds));
+        assert_eq!(types[1].string_kind, None);
+        let is_speech = &types[3];
+        assert!(is_speech.optional);
+        assert_eq!(is_speech.default.as_deref(), Some("[[false]]"));
+        assert_eq!(
+            is_speech.since.as_ref().unwrap().arma_3(),
+            Some(&Version::new(1, 92))
+        );
+        let mut since = Since::default();
+        since.set_arma_3(Some(Version::new(2, 4)));
+        assert_eq!(
+            is_speech.value,
+            Value::OneOf(vec![(Value::Boolean, None), (Value::Number, Some(since))])
+        );
     }
 }
@@ -0,0 +1,377 @@
+use super::{Arg, Call, Param, Syntax, Value, syntax::find_param};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// How well the operands of a call site match a syntax, best first.
+pub enum MatchQuality {
+    /// Every operand has exactly the documented type.
+    Exact,
+    /// Every operand is accepted by the documented type, but not all of them are the same type.
+    Compatible,
+    /// At least one operand or documented type is unknown, so the match could not be checked.
+    Unchecked,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Left,
+    Right,
+}
+
+impl std::fmt::Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Why a syntax was rejected for a call site.
+pub enum Rejection {
+    /// The syntax takes different operands than the call site has.
+    Arity { expected: &'static str },
+    /// An operand, or an element of an array operand, has the wrong type.
+    Type {
+        operand: Operand,
+        param: String,
+        expected: Value,
+        found: Value,
+    },
+    /// An array operand has more elements than the syntax takes.
+    TooManyElements {
+        operand: Operand,
+        expected: usize,
+        found: usize,
+    },
+    /// An array operand is missing a required element.
+    MissingElement { operand: Operand, param: String },
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Arity { expected } => write!(f, "expected a {expected} call"),
+            Self::Type {
+                operand,
+                param,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{operand} operand `{param}` expects {expected}, found {found}"
+            ),
+            Self::TooManyElements {
+                operand,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{operand} operand expects at most {expected} elements, found {found}"
+            ),
+            Self::MissingElement { operand, param } => {
+                write!(f, "{operand} operand is missing `{param}`")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// The result of [`super::Command::resolve`].
+pub struct Resolution<'a> {
+    /// The syntaxes matching the call site, best first.
+    pub matches: Vec<(&'a Syntax, MatchQuality)>,
+    /// The syntaxes that do not match the call site, with the reason.
+    pub rejected: Vec<(&'a Syntax, Rejection)>,
+}
+
+impl<'a> Resolution<'a> {
+    #[must_use]
+    pub fn best(&self) -> Option<&'a Syntax> {
+        self.matches.first().map(|(syntax, _)| *syntax)
+    }
+
+    #[must_use]
+    /// The return type of the call site, if every matching syntax of the best quality agrees on it.
+    pub fn ret(&self) -> Option<&'a Value> {
+        let (_, quality) = self.matches.first()?;
+        let mut best = self
+            .matches
+            .iter()
+            .take_while(|(_, q)| q == quality)
+            .map(|(syntax, _)| &syntax.ret().0);
+        let ret = best.next()?;
+        best.all(|other| other == ret).then_some(ret)
+    }
+}
+
+pub fn resolve<'a>(
+    syntaxes: &'a [Syntax],
+    lhs: Option<&Value>,
+    rhs: Option<&Value>,
+) -> Resolution<'a> {
+    let mut resolution = Resolution::default();
+    for syntax in syntaxes {
+        match check_syntax(syntax, lhs, rhs) {
+            Ok(quality) => resolution.matches.push((syntax, quality)),
+            Err(rejection) => resolution.rejected.push((syntax, rejection)),
+        }
+    }
+    resolution.matches.sort_by_key(|(_, quality)| *quality);
+    resolution
+}
+
+fn check_syntax(
+    syntax: &Syntax,
+    lhs: Option<&Value>,
+    rhs: Option<&Value>,
+) -> Result<MatchQuality, Rejection> {
+    let params = syntax.params();
+    match (syntax.call(), lhs, rhs) {
+        (Call::Nular, None, None) => Ok(MatchQuality::Exact),
+        (Call::Unary(arg), None, Some(rhs)) => check_arg(params, Operand::Right, arg, rhs),
+        (Call::Binary(left, right), Some(lhs), Some(rhs)) => {
+            let left = check_arg(params, Operand::Left, left, lhs)?;
+            let right = check_arg(params, Operand::Right, right, rhs)?;
+            Ok(left.max(right))
+        }
+        (call, _, _) => Err(Rejection::Arity {
+            expected: match call {
+                Call::Nular => "nular",
+                Call::Unary(_) => "unary",
+                Call::Binary(_, _) => "binary",
+            },
+        }),
+    }
+}
+
+fn check_arg(
+    params: &[Param],
+    operand: Operand,
+    arg: &Arg,
+    found: &Value,
+) -> Result<MatchQuality, Rejection> {
+    match arg {
+        Arg::Item(name) => {
+            let Some(param) = find_param(params, name) else {
+                return Ok(MatchQuality::Unchecked);
+            };
+            check_value(param.typ(), found).ok_or_else(|| Rejection::Type {
+                operand,
+                param: name.clone(),
+                expected: param.typ().clone(),
+                found: found.clone(),
+            })
+        }
+        Arg::Array(args) => match found {
+            Value::ArraySized { types, .. } => {
+                if let Some((_, Some(max))) = arg.arity()
+                    && types.len() > max
+                {
+                    return Err(Rejection::TooManyElements {
+                        operand,
+                        expected: max,
+                        found: types.len(),
+                    });
+                }
+                let mut quality = MatchQuality::Exact;
+                for (i, arg) in args.iter().enumerate() {
+                    if arg == &Arg::Variadic {
+                        break;
+                    }
+                    if let Some(element) = types.get(i) {
+                        quality = quality.max(check_arg(params, operand, arg, &element.value)?);
+                    } else if let Some(name) = required(params, arg) {
+                        return Err(Rejection::MissingElement {
+                            operand,
+                            param: name.to_string(),
+                        });
+                    }
+                }
+                Ok(quality)
+            }
+            Value::ArrayUnknown | Value::Anything | Value::Unknown => Ok(MatchQuality::Unchecked),
+            found if found.is_assignable_to(&Value::ArrayUnknown) => Ok(MatchQuality::Compatible),
+            _ => Err(Rejection::Type {
+                operand,
+                param: arg.names().join(", "),
+                expected: Value::ArrayUnknown,
+                found: found.clone(),
+            }),
+        },
+        Arg::Optional(arg) => check_arg(params, operand, arg, found),
+        Arg::Variadic => Ok(MatchQuality::Unchecked),
+    }
+}
+
+/// Checks a single value against the documented type.
+fn check_value(expected: &Value, found: &Value) -> Option<MatchQuality> {
+    match (expected, found) {
+        (Value::Unknown, _) | (_, Value::Unknown | Value::Anything) => {
+            Some(MatchQuality::Unchecked)
+        }
+        _ if expected == found => Some(MatchQuality::Exact),
+        _ if found.is_assignable_to(expected) => Some(MatchQuality::Compatible),
+        // an array of unknown shape may still be the documented kind of array
+        (_, Value::ArrayUnknown) if expected.is_assignable_to(&Value::ArrayUnknown) => {
+            Some(MatchQuality::Unchecked)
+        }
+        _ => None,
+    }
+}
+
+/// The name of the first required param in an array element, if any.
+fn required<'a>(params: &[Param], arg: &'a Arg) -> Option<&'a str> {
+    match arg {
+        Arg::Item(name) => find_param(params, name)
+            .is_some_and(|p| !p.optional())
+            .then_some(name.as_str()),
+        Arg::Array(args) => args.iter().find_map(|arg| required(params, arg)),
+        Arg::Optional(_) | Arg::Variadic => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchQuality, Operand, Rejection};
+    use crate::model::{Arg, ArraySizedElement, Call, Command, Param, Syntax, Value};
+
+    fn param(name: &str, typ: Value, optional: bool) -> Param {
+        Param::new(name.to_string(), None, typ, optional, None, None)
+    }
+
+    fn item(name: &str) -> Arg {
+        Arg::Item(name.to_string())
+    }
+
+    fn element(value: Value) -> ArraySizedElement {
+        ArraySizedElement {
+            name: String::new(),
+            value,
+            desc: String::new(),
+            since: None,
+            optional: false,
+            default: None,
+            string_kind: None,
+        }
+    }
+
+    fn set_pos() -> Command {
+        let mut command = Command::default();
+        command.set_name("setPos".to_string());
+        command.set_syntax(vec![
+            Syntax::new(
+                Call::Binary(item("object"), item("pos")),
+                (Value::Nothing, None),
+                vec![
+                    param("object", Value::Object, false),
+                    param("pos", Value::Position, false),
+                ],
+                None,
+                None,
+            ),
+            Syntax::new(
+                Call::Binary(item("group"), Arg::Array(vec![item("x"), item("y")])),
+                (Value::Boolean, None),
+                vec![
+                    param("group", Value::Group, false),
+                    param("x", Value::Number, false),
+                    param("y", Value::Number, true),
+                ],
+                None,
+                None,
+            ),
+        ]);
+        command
+    }
+
+    #[test]
+    fn exact() {
+        let command = set_pos();
+        let resolution = command.resolve(Some(&Value::Object), Some(&Value::Position));
+        assert_eq!(resolution.matches.len(), 1);
+        assert_eq!(resolution.matches[0].1, MatchQuality::Exact);
+        assert_eq!(resolution.ret(), Some(&Value::Nothing));
+        assert_eq!(
+            resolution.rejected[0].1,
+            Rejection::Type {
+                operand: Operand::Left,
+                param: "group".to_string(),
+                expected: Value::Group,
+                found: Value::Object,
+            }
+        );
+    }
+
+    #[test]
+    fn array() {
+        let command = set_pos();
+        let array = Value::ArraySized {
+            types: vec![element(Value::Number)],
+            desc: String::new(),
+        };
+        let resolution = command.resolve(Some(&Value::Group), Some(&array));
+        assert_eq!(resolution.ret(), Some(&Value::Boolean));
+
+        let array = Value::ArraySized {
+            types: vec![element(Value::Number); 3],
+            desc: String::new(),
+        };
+        let resolution = command.resolve(Some(&Value::Group), Some(&array));
+        assert!(resolution.matches.is_empty());
+        assert_eq!(
+            resolution.rejected[1].1,
+            Rejection::TooManyElements {
+                operand: Operand::Right,
+                expected: 2,
+                found: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn numbered() {
+        let mut command = Command::default();
+        command.set_name("format".to_string());
+        command.set_syntax(vec![Syntax::new(
+            Call::Unary(Call::parse_params("[format, argument1, ...]").unwrap()),
+            (Value::String, None),
+            vec![
+                param("format", Value::String, false),
+                param("argumentN", Value::Number, true),
+            ],
+            None,
+            None,
+        )]);
+        let array = |types: Vec<Value>| Value::ArraySized {
+            types: types.into_iter().map(element).collect(),
+            desc: String::new(),
+        };
+        let resolution = command.resolve(None, Some(&array(vec![Value::String, Value::Number])));
+        assert_eq!(resolution.matches[0].1, MatchQuality::Exact);
+        assert_eq!(
+            command
+                .resolve(None, Some(&array(vec![Value::String, Value::Boolean])))
+                .rejected[0]
+                .1,
+            Rejection::Type {
+                operand: Operand::Right,
+                param: "argument1".to_string(),
+                expected: Value::Number,
+                found: Value::Boolean,
+            }
+        );
+    }
+
+    #[test]
+    fn unchecked() {
+        let command = set_pos();
+        let resolution = command.resolve(Some(&Value::Anything), Some(&Value::ArrayUnknown));
+        assert_eq!(resolution.matches.len(), 2);
+        assert_eq!(resolution.ret(), None);
+        assert!(matches!(
+            command.resolve(None, Some(&Value::Object)).rejected[0].1,
+            Rejection::Arity { expected: "binary" }
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::Version;
+use super::{Game, Version};
 
 #[derive(Clone, Default, Hash, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Since {
@@ -28,6 +28,10 @@ pub struct Since {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     argo: Option<Version>,
+    /// The game and version the item was replaced in, from a `(before {{GVI|..}})` qualifier.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    until: Option<(Game, Version)>,
 }
 
 impl Since {
@@ -103,6 +107,70 @@ impl Since {
         self.argo = argo;
     }
 
+    #[must_use]
+    pub const fn until(&self) -> Option<&(Game, Version)> {
+        self.until.as_ref()
+    }
+
+    pub fn set_until(&mut self, until: Option<(Game, Version)>) {
+        self.until = until;
+    }
+
+    #[must_use]
+    /// The version a game introduced the item in, if documented.
+    pub const fn version(&self, game: Game) -> Option<&Version> {
+        match game {
+            Game::Flashpoint => self.flashpoint.as_ref(),
+            Game::FlashpointElite => self.flashpoint_elite.as_ref(),
+            Game::ArmedAssault => self.armed_assault.as_ref(),
+            Game::Arma2 => self.arma_2.as_ref(),
+            Game::Arma2Arrowhead => self.arma_2_arrowhead.as_ref(),
+            Game::TakeOnHelicopters => self.take_on_helicopters.as_ref(),
+            Game::Arma3 => self.arma_3.as_ref(),
+            Game::Argo => self.argo.as_ref(),
+        }
+    }
+
+    #[must_use]
+    /// Whether the item is available in a version of a game.
+    ///
+    /// An item is only available in the games it has a version for, such as an Arma 3 only param
+    /// not being available in Argo. Items without any version are always available.
+    /// An item with an upper bound is unavailable from that version on, and in later games.
+    pub fn is_available_in(&self, game: Game, version: &Version) -> bool {
+        if let Some((until_game, until)) = &self.until
+            && (game > *until_game || (game == *until_game && version >= until))
+        {
+            return false;
+        }
+        self.version(game)
+            .map_or_else(|| self.iter().next().is_none(), |since| since <= version)
+    }
+
+    pub fn set(&mut self, game: Game, version: Option<Version>) {
+        match game {
+            Game::Flashpoint => self.flashpoint = version,
+            Game::FlashpointElite => self.flashpoint_elite = version,
+            Game::ArmedAssault => self.armed_assault = version,
+            Game::Arma2 => self.arma_2 = version,
+            Game::Arma2Arrowhead => self.arma_2_arrowhead = version,
+            Game::TakeOnHelicopters => self.take_on_helicopters = version,
+            Game::Arma3 => self.arma_3 = version,
+            Game::Argo => self.argo = version,
+        }
+    }
+
+    /// The documented versions, in release order of the games.
+    pub fn iter(&self) -> impl Iterator<Item = (Game, &Version)> {
+        Game::iter().filter_map(|game| self.version(*game).map(|version| (*game, version)))
+    }
+
+    #[must_use]
+    /// The first game and version the item appeared in.
+    pub fn earliest(&self) -> Option<(Game, &Version)> {
+        self.iter().next()
+    }
+
     #[cfg(feature = "wiki")]
     /// Sets the version from the wiki.
     ///
@@ -117,35 +185,50 @@ impl Since {
     /// # Errors
     /// Returns an error if the key is unknown.
     pub fn set_version(&mut self, key: &str, version: Version) -> Result<(), String> {
-        match key.to_lowercase().as_str() {
-            "ofp" => {
-                self.set_flashpoint(Some(version));
-            }
-            "ofpe" => {
-                self.set_flashpoint_elite(Some(version));
-            }
-            "arma1" => {
-                self.set_armed_assault(Some(version));
-            }
-            "arma2" => {
-                self.set_arma_2(Some(version));
-            }
-            "arma2oa" => {
-                self.set_arma_2_arrowhead(Some(version));
-            }
-            "tkoh" => {
-                self.set_take_on_helicopters(Some(version));
-            }
-            "arma3" => {
-                self.set_arma_3(Some(version));
-            }
-            "argo" => {
-                self.set_argo(Some(version));
-            }
-            _ => {
-                return Err(format!("Unknown since key: {key}"));
-            }
-        }
+        let game = Game::from_wiki_key(key).ok_or_else(|| format!("Unknown since key: {key}"))?;
+        self.set(game, Some(version));
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Since;
+    use crate::model::{Game, Version};
+
+    #[test]
+    fn games() {
+        let mut since = Since::default();
+        assert_eq!(since.earliest(), None);
+        assert!(since.is_available_in(Game::Flashpoint, &Version::new(1, 0)));
+
+        since.set_version("arma3", Version::new(0, 50)).unwrap();
+        since.set_version("ARMA2OA", Version::new(1, 50)).unwrap();
+        assert!(since.set_version("dayz", Version::new(1, 0)).is_err());
+        assert_eq!(since.arma_2_arrowhead(), Some(&Version::new(1, 50)));
+        assert_eq!(
+            since.iter().collect::<Vec<_>>(),
+            [
+                (Game::Arma2Arrowhead, &Version::new(1, 50)),
+                (Game::Arma3, &Version::new(0, 50))
+            ]
+        );
+        assert_eq!(
+            since.earliest(),
+            Some((Game::Arma2Arrowhead, &Version::new(1, 50)))
+        );
+        assert!(since.is_available_in(Game::Arma2Arrowhead, &Version::new(1, 62)));
+        assert!(since.is_available_in(Game::Arma3, &Version::new(2, 18)));
+        assert!(!since.is_available_in(Game::TakeOnHelicopters, &Version::new(1, 0)));
+        assert!(!since.is_available_in(Game::Argo, &Version::new(1, 0)));
+        assert!(!since.is_available_in(Game::Arma2, &Version::new(1, 62)));
+        assert!(!since.is_available_in(Game::Arma3, &Version::new(0, 40)));
+
+        assert_eq!(
+            Game::Arma2Arrowhead.to_string(),
+            "Arma 2: Operation Arrowhead"
+        );
+        assert_eq!(Game::from_wiki_key("tkoh"), Some(Game::TakeOnHelicopters));
+        assert!(Game::iter().zip(Game::iter().skip(1)).all(|(a, b)| a < b));
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(feature = "wiki")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "wiki")]
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "wiki")]
+use super::Value;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+/// What a [`super::Value::String`] holds, when the wiki says more than "a string".
+pub enum StringKind {
+    /// A class name, in the config class when known, such as `CfgVehicles`.
+    ClassName(Option<String>),
+    FilePath,
+    /// The name of a variable.
+    Identifier,
+    FunctionName,
+}
+
+#[cfg(feature = "wiki")]
+static REGEX_CONFIG: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_CLASS_NAME: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_FILE_PATH: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_IDENTIFIER: OnceLock<Regex> = OnceLock::new();
+#[cfg(feature = "wiki")]
+static REGEX_FUNCTION_NAME: OnceLock<Regex> = OnceLock::new();
+
+impl StringKind {
+    #[cfg(feature = "wiki")]
+    #[must_use]
+    /// Recognizes the kind of string from the description of a param or array element of type `typ`.
+    ///
+    /// Returns `None` if `typ` cannot be a string.
+    ///
+    /// # Panics
+    /// Panics if the regex fails to compile.
+    pub fn from_wiki(typ: &Value, source: &str) -> Option<Self> {
+        let holds_string = match typ {
+            Value::String => true,
+            Value::OneOf(alternatives) => alternatives.iter().any(|(v, _)| v == &Value::String),
+            _ => false,
+        };
+        if !holds_string {
+            return None;
+        }
+        let regex_config = REGEX_CONFIG.get_or_init(|| Regex::new(r"\b(Cfg[A-Z]\w+)").unwrap());
+        let regex_class_name = REGEX_CLASS_NAME
+            .get_or_init(|| Regex::new(r"(?i)\bclass ?names?\b|\bname of the class\b").unwrap());
+        let regex_file_path = REGEX_FILE_PATH.get_or_init(|| {
+            Regex::new(r"(?i)\bfile ?(?:path|name)s?\b|\bpath to (?:the |a )?(?:file|script|image|texture|model|sound)\b|\.(?:sqf|sqs|paa|jpg|p3d|ogg|wss|fsm)\b").unwrap()
+        });
+        let regex_identifier = REGEX_IDENTIFIER.get_or_init(|| {
+            Regex::new(r"(?i)\bvariable name\b|\bname of (?:the |a )?variable\b|\[\[Identifier\]\]")
+                .unwrap()
+        });
+        let regex_function_name = REGEX_FUNCTION_NAME.get_or_init(|| {
+            Regex::new(r"(?i)\bfunction (?:or command )?name\b|\bname of (?:the |a )?function\b")
+                .unwrap()
+        });
+
+        let config = regex_config
+            .captures(source)
+            .map(|caps| caps[1].to_string());
+        if regex_class_name.is_match(source) || config.is_some() {
+            Some(Self::ClassName(config))
+        } else if regex_function_name.is_match(source) {
+            Some(Self::FunctionName)
+        } else if regex_identifier.is_match(source) {
+            Some(Self::Identifier)
+        } else if regex_file_path.is_match(source) {
+            Some(Self::FilePath)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for StringKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ClassName(Some(config)) => write!(f, "Class Name ({config})"),
+            Self::ClassName(None) => write!(f, "Class Name"),
+            Self::FilePath => write!(f, "File Path"),
+            Self::Identifier => write!(f, "Identifier"),
+            Self::FunctionName => write!(f, "Function Name"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "wiki")]
+mod tests {
+    use super::StringKind;
+    use crate::model::Value;
+
+    #[test]
+    fn from_wiki() {
+        assert_eq!(
+            StringKind::from_wiki(
+                &Value::String,
+                "classname of the sound to be played. Defined in [[Description.ext#CfgSounds|CfgSounds]]"
+            ),
+            Some(StringKind::ClassName(Some("CfgSounds".to_string())))
+        );
+        assert_eq!(
+            StringKind::from_wiki(&Value::String, "vehicle class name"),
+            Some(StringKind::ClassName(None))
+        );
+        assert_eq!(
+            StringKind::from_wiki(
+                &Value::String,
+                "path to the script file, e.g. \"scripts\\init.sqf\""
+            ),
+            Some(StringKind::FilePath)
+        );
+        assert_eq!(
+            StringKind::from_wiki(
+                &Value::String,
+                "interpreted as an [[Identifier]] (variable name)"
+            ),
+            Some(StringKind::Identifier)
+        );
+        assert_eq!(
+            StringKind::from_wiki(&Value::String, "function or command name"),
+            Some(StringKind::FunctionName)
+        );
+        assert_eq!(
+            StringKind::from_wiki(&Value::String, "text of the hint"),
+            None
+        );
+        assert_eq!(StringKind::from_wiki(&Value::String, "turret path"), None);
+        assert_eq!(
+            StringKind::from_wiki(&Value::Number, "vehicle class name"),
+            None
+        );
+    }
+}
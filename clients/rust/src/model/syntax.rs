@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use super::{Call, Locality, Param, ParseError, Since, Value};
+#[cfg(feature = "wiki")]
+use super::ParseError;
+use super::{Arg, Bindings, Call, Game, Locality, NumberConstraint, Param, Since, Value, Version};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SyntaxRepr")]
 pub struct Syntax {
     pub(crate) call: Call,
     pub(crate) ret: (Value, Option<String>),
@@ -13,6 +16,39 @@ pub struct Syntax {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) effect: Option<Locality>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ret_constraint: Option<NumberConstraint>,
+}
+
+/// How a [`Syntax`] is read, before the optional arguments of its call are marked from its params.
+#[derive(Deserialize)]
+struct SyntaxRepr {
+    call: Call,
+    ret: (Value, Option<String>),
+    params: Vec<Param>,
+    #[serde(default)]
+    since: Option<Since>,
+    #[serde(default)]
+    effect: Option<Locality>,
+    #[serde(default)]
+    ret_constraint: Option<NumberConstraint>,
+}
+
+impl From<SyntaxRepr> for Syntax {
+    fn from(repr: SyntaxRepr) -> Self {
+        let call = repr
+            .call
+            .with_optional(&|name| find_param(&repr.params, name).is_some_and(Param::optional));
+        Self {
+            call,
+            ret: repr.ret,
+            params: repr.params,
+            since: repr.since,
+            effect: repr.effect,
+            ret_constraint: repr.ret_constraint,
+        }
+    }
 }
 
 impl Syntax {
@@ -30,6 +66,7 @@ impl Syntax {
             params,
             since,
             effect,
+            ret_constraint: None,
         }
     }
 
@@ -43,11 +80,56 @@ impl Syntax {
         &self.ret
     }
 
+    #[must_use]
+    /// The documented limits of a number return value.
+    pub const fn ret_constraint(&self) -> Option<&NumberConstraint> {
+        self.ret_constraint.as_ref()
+    }
+
     #[must_use]
     pub fn params(&self) -> &[Param] {
         &self.params
     }
 
+    #[must_use]
+    /// The operands of the call, with each argument resolved to its param.
+    pub fn bindings(&self) -> Bindings<'_> {
+        Bindings::new(&self.call, &|name| find_param(&self.params, name))
+    }
+
+    #[must_use]
+    /// The syntax as documented in a version of a game, or `None` if it is newer.
+    ///
+    /// Newer params are left out, along with their arguments in the call.
+    pub fn at_version(&self, game: Game, version: &Version) -> Option<Self> {
+        if self
+            .since
+            .as_ref()
+            .is_some_and(|since| !since.is_available_in(game, version))
+        {
+            return None;
+        }
+        let newer = |name: &str| {
+            find_param(&self.params, name).is_some_and(|p| p.at_version(game, version).is_none())
+        };
+        let call = match &self.call {
+            Call::Nular => Call::Nular,
+            Call::Unary(arg) => Call::Unary(without(arg, &newer)?),
+            Call::Binary(left, right) => {
+                Call::Binary(without(left, &newer)?, without(right, &newer)?)
+            }
+        };
+        Some(Self {
+            call,
+            params: self
+                .params
+                .iter()
+                .filter_map(|p| p.at_version(game, version))
+                .collect(),
+            ..self.clone()
+        })
+    }
+
     #[must_use]
     pub const fn since(&self) -> Option<&Since> {
         self.since.as_ref()
@@ -65,6 +147,10 @@ impl Syntax {
         self.ret = ret;
     }
 
+    pub fn set_ret_constraint(&mut self, ret_constraint: Option<NumberConstraint>) {
+        self.ret_constraint = ret_constraint;
+    }
+
     pub fn set_params(&mut self, params: Vec<Param>) {
         self.params = params;
     }
@@ -131,76 +217,119 @@ impl Syntax {
                 break;
             }
         }
-        let call = Call::from_wiki(usage)?;
-        let mut list = false;
+        let call = Call::from_wiki(usage)?
+            .with_optional(&|name| find_param(&params, name).is_some_and(Param::optional));
         for arg in call.param_names() {
-            if arg == "..." && list {
-                continue;
-            }
             if command == "throw" && arg == "if (condition)" {
                 continue;
             }
-            if !params.iter().any(|p| p.name() == arg) {
-                // check if arguments are numbered (argument1, argument2)
-                // then check if the param is argumentN
-                // generic over argument
-                let mut root = String::new();
-                for c in arg.chars() {
-                    if c.is_numeric() {
-                        break;
-                    }
-                    root.push(c);
-                }
-                root.push('N');
-                if !params.iter().any(|p| p.name() == root) {
-                    println!("params: {params:?}");
-                    return Err(format!("Missing param: {arg}"));
-                }
-                list = true;
+            if find_param(&params, &arg).is_none() {
+                println!("params: {params:?}");
+                return Err(format!("Missing param: {arg}"));
             }
         }
-        Ok((
-            Self::new(
-                call,
-                {
-                    let Some(mut ret) = ret else {
-                        return Err("Missing return".to_string());
+        let ret_constraint = ret.as_deref().and_then(NumberConstraint::from_wiki);
+        let mut syntax = Self::new(
+            call,
+            {
+                let Some(mut ret) = ret else {
+                    return Err("Missing return".to_string());
+                };
+                if ret.contains("\n{{") {
+                    let Some((ret_trim, _)) = ret.split_once("\n{{") else {
+                        return Err(format!("Invalid return: {ret}"));
                     };
-                    if ret.contains("\n{{") {
-                        let Some((ret_trim, _)) = ret.split_once("\n{{") else {
-                            return Err(format!("Invalid return: {ret}"));
-                        };
-                        ret = ret_trim.trim().to_string();
-                    }
-                    if ret.contains(" format") {
-                        Value::match_explicit(&ret).map_or_else(
-                            || {
-                                errors.push(ParseError::Syntax(ret));
-                                (Value::Unknown, None)
-                            },
-                            |explicit_match| (explicit_match, None),
-                        )
-                    } else {
-                        let (typ, desc) = ret.split_once('-').unwrap_or((&ret, ""));
-                        let typ = typ.trim();
-                        (
-                            Value::from_wiki(typ).unwrap_or_else(|_| {
-                                errors.push(ParseError::UnknownType(typ.to_string()));
-                                Value::Unknown
-                            }),
-                            if desc.is_empty() {
-                                None
-                            } else {
-                                Some(desc.trim().to_string())
-                            },
-                        )
-                    }
-                },
-                params,
-                since,
-                effect,
+                    ret = ret_trim.trim().to_string();
+                }
+                if ret.contains(" format") {
+                    // the description follows the type on the first line, before any element bullets
+                    let desc = ret
+                        .lines()
+                        .next()
+                        .and_then(|header| header.split_once(" - "))
+                        .map(|(_, desc)| desc.trim().to_string())
+                        .filter(|desc| !desc.is_empty());
+                    Value::from_wiki(&ret).ok().map_or_else(
+                        || {
+                            errors.push(ParseError::Syntax(ret));
+                            (Value::Unknown, None)
+                        },
+                        |explicit_match| (explicit_match, desc),
+                    )
+                } else {
+                    let (typ, desc) = ret.split_once('-').unwrap_or((&ret, ""));
+                    let typ = typ.trim();
+                    (
+                        Value::from_wiki(typ).unwrap_or_else(|_| {
+                            errors.push(ParseError::UnknownType(typ.to_string()));
+                            Value::Unknown
+                        }),
+                        if desc.is_empty() {
+                            None
+                        } else {
+                            Some(desc.trim().to_string())
+                        },
+                    )
+                }
+            },
+            params,
+            since,
+            effect,
+        );
+        if syntax.ret.0 == Value::Number {
+            syntax.ret_constraint = ret_constraint;
+        }
+        Ok((syntax, errors))
+    }
+}
+
+/// Finds the param documenting an argument.
+///
+/// Numbered arguments, such as `argument1` and `argument2`, are documented by a single `argumentN` param.
+pub(super) fn find_param<'a>(params: &'a [Param], name: &str) -> Option<&'a Param> {
+    params.iter().find(|p| p.name() == name).or_else(|| {
+        let root = name.split(char::is_numeric).next().unwrap_or(name);
+        let numbered = format!("{root}N");
+        params.iter().find(|p| p.name() == numbered)
+    })
+}
+
+/// Removes the arguments matching `remove`, or returns `None` if the whole argument is removed.
+fn without(arg: &Arg, remove: &impl Fn(&str) -> bool) -> Option<Arg> {
+    match arg {
+        Arg::Item(name) if remove(name) => None,
+        Arg::Array(args) => Some(Arg::Array(
+            args.iter().filter_map(|arg| without(arg, remove)).collect(),
+        )),
+        Arg::Optional(arg) => without(arg, remove).map(|arg| Arg::Optional(Box::new(arg))),
+        arg => Some(arg.clone()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "wiki")]
+mod tests {
+    use super::Syntax;
+    use crate::model::Value;
+
+    #[test]
+    fn format_return_description() {
+        let mut lines = vec![
+            ("p1", "unit: [[Object]] - the unit"),
+            (
+                "r1",
+                "[[Array]] format [[Position#PositionAGL|PositionAGL]] - translated world position",
             ),
-            errors,
-        ))
+        ]
+        .into_iter()
+        .peekable();
+        let (syntax, _) = Syntax::from_wiki("getPos", "[[getPos]] unit", &mut lines).unwrap();
+        assert_eq!(
+            syntax.ret(),
+            &(
+                Value::Position3dAGL,
+                Some("translated world position".to_string())
+            )
+        );
     }
 }
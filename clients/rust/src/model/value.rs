@@ -1,9 +1,13 @@
+#[cfg(feature = "wiki")]
 use std::sync::OnceLock;
 
+#[cfg(feature = "wiki")]
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use super::Since;
+#[cfg(feature = "wiki")]
+use super::wiki;
+use super::{CodeSignature, Game, Since, StringKind, Version};
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArraySizedElement {
@@ -11,6 +15,15 @@ pub struct ArraySizedElement {
     pub value: Value,
     pub desc: String,
     pub since: Option<Since>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string_kind: Option<StringKind>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +45,8 @@ pub enum Value {
     ArrayColorRgba,
     Boolean,
     Code,
+    /// Code with a documented `_this`, variables or return type.
+    CodeWithSignature(Box<CodeSignature>),
     Config,
     Control,
     DiaryRecord,
@@ -79,6 +94,7 @@ pub enum Value {
 }
 
 // regex once cell
+#[cfg(feature = "wiki")]
 static REGEX_TYPE: OnceLock<Regex> = OnceLock::new();
 // static REGEX_ARRAY_IN_FORMAT: OnceLock<Regex> = OnceLock::new();
 
@@ -92,22 +108,189 @@ impl Value {
     /// # Panics
     /// Panics if the regex fails to compile.
     pub fn from_wiki(source: &str) -> Result<Self, String> {
-        if let Some(explicit_match) = Self::match_explicit(source) {
-            return Ok(explicit_match);
+        if let Some(value) = Self::match_type(source)
+            .or_else(|| Self::match_sized(source))
+            .or_else(|| Self::match_one_of(source))
+        {
+            return Ok(value);
+        }
+        println!("unable to parse value: {source}");
+        Err("Unknown value".to_string())
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Matches a source describing exactly one type.
+    fn match_type(source: &str) -> Option<Self> {
+        Self::match_explicit(source)
+            .or_else(|| Self::match_single(source))
+            .or_else(|| Self::match_array_of(source))
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Matches `[[Array]] of [[Type]]s`, where the element type may itself be an array.
+    ///
+    /// Anything following the element type is kept as the description of the array.
+    fn match_array_of(source: &str) -> Option<Self> {
+        let source = source.trim();
+        let elements = source
+            .strip_prefix("[[Array]] of ")
+            .or_else(|| source.strip_prefix("[[Array]]s of "))?
+            .trim_start_matches("GUI ");
+        let (typ, desc) = if elements.contains(" or ") || elements.contains(" and/or ") {
+            (
+                // alternatives are plural too, as in `[[Number]]s or [[String]]s`
+                Self::match_one_of(&elements.replace(" and/or ", " or ").replace("]]s", "]]"))?,
+                "",
+            )
+        } else if elements.starts_with("[[Array]] of ") || elements.starts_with("[[Array]]s of ") {
+            (Self::match_array_of(elements)?, "")
+        } else if elements.starts_with("[[") {
+            let end = elements.find("]]")? + 2;
+            let rest = &elements[end..];
+            let rest = rest
+                .strip_prefix('s')
+                .filter(|rest| !rest.starts_with(char::is_alphanumeric))
+                .unwrap_or(rest);
+            (Self::match_type(&elements[..end])?, rest)
+        } else {
+            // a plain word, such as `string` or `numbers`
+            let end = elements
+                .find(|c: char| !c.is_alphanumeric())
+                .unwrap_or(elements.len());
+            let word = &elements[..end];
+            let typ = Self::single_match(word)
+                .or_else(|_| Self::single_match(word.strip_suffix('s').unwrap_or(word)))
+                .ok()?;
+            (typ, &elements[end..])
+        };
+        Some(Self::ArrayUnsized {
+            typ: Box::new(typ),
+            desc: desc
+                .trim_start()
+                .trim_start_matches([',', '-', '–'])
+                .trim()
+                .to_string(),
+        })
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Matches `[[Array]] format [a, b, c] where:` followed by a bullet describing each element,
+    /// written as `* name: [[Type]] - (Optional, default x) description`.
+    fn match_sized(source: &str) -> Option<Self> {
+        let (header, elements) = source.split_once('\n')?;
+        let header = header.trim();
+        if !header.starts_with("[[Array]]") || !header.contains("format [") {
+            return None;
+        }
+        let types = wiki::bullets(elements)
+            .into_iter()
+            .map(|(line, details)| Self::sized_element(line, &details))
+            .collect::<Vec<_>>();
+        if types.is_empty() {
+            return None;
+        }
+        Some(Self::ArraySized {
+            types,
+            desc: header
+                .split_once(" - ")
+                .map(|(_, desc)| desc.trim().to_string())
+                .unwrap_or_default(),
+        })
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Parses the bullet documenting one element of a sized array.
+    ///
+    /// Element types that cannot be parsed are kept as `Unknown`, so the shape of the array is not lost.
+    pub(super) fn sized_element(line: &str, details: &[String]) -> ArraySizedElement {
+        let (since, line) = wiki::strip_since(line);
+        let (name, rest) = match (line.find(':'), line.find("[[")) {
+            (Some(colon), first_type) if first_type.is_none_or(|start| colon < start) => {
+                (line[..colon].trim().trim_matches('\''), &line[colon + 1..])
+            }
+            _ => ("", line.as_str()),
+        };
+        let (typ, desc) = rest.split_once(" - ").unwrap_or((rest, ""));
+        let typ = typ.trim();
+        let value = Self::match_type(typ)
+            .or_else(|| Self::match_sized(&format!("{typ}\n{}", details.join("\n"))))
+            .or_else(|| Self::match_one_of(typ))
+            .unwrap_or(Self::Unknown);
+        let desc = desc.trim();
+        let (optional, default, desc) = match desc
+            .strip_prefix("(Optional")
+            .and_then(|rest| rest.split_once(')'))
+        {
+            Some((default, desc)) => (
+                true,
+                default
+                    .strip_prefix(", default ")
+                    .map(|default| default.trim().to_string()),
+                desc.trim(),
+            ),
+            None => (false, None, desc),
+        };
+        ArraySizedElement {
+            name: name.to_string(),
+            string_kind: StringKind::from_wiki(&value, desc),
+            value,
+            desc: desc.to_string(),
+            since,
+            optional,
+            default,
         }
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Matches a source that is entirely a single `[[Type]]` link.
+    fn match_single(source: &str) -> Option<Self> {
         let regex_type = REGEX_TYPE.get_or_init(|| Regex::new(r"(?m)\[\[([^\[\]]+)\]\]").unwrap());
-        // let regex_array_in_format = REGEX_ARRAY_IN_FORMAT
-        //     .get_or_init(|| Regex::new(r"(?m)\[\[Array\]\] in format \[\[([^\[\]]+)\]\]").unwrap());
-
-        // Check if the entire type is just a single value
-        if let Some(caps) = regex_type.captures(source) {
-            let span = caps.get(0).unwrap().range();
-            if span.start == 0 && span.end == source.len() {
-                return Self::single_match(caps.get(1).unwrap().as_str());
+        let caps = regex_type.captures(source)?;
+        let span = caps.get(0).unwrap().range();
+        if span.start == 0 && span.end == source.len() {
+            Self::single_match(caps.get(1).unwrap().as_str()).ok()
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "wiki")]
+    /// Matches a list of alternative types, either as a bullet list, or inline as
+    /// `[[Number]], [[Object]] or [[Array]]`.
+    ///
+    /// Alternatives qualified with `(since {{GVI|..}})` or prefixed with a `{{GVI|..}}` icon
+    /// keep that version. Alternatives qualified with `(before {{GVI|..}})` keep that version
+    /// as [`Since::until`], the version they were replaced in.
+    fn match_one_of(source: &str) -> Option<Self> {
+        let mut alternatives: Vec<(Self, Option<Since>)> = Vec::new();
+        if source.contains("\n*") {
+            for (line, details) in wiki::bullets(source) {
+                let (since, line) = wiki::strip_since(line);
+                let end = [line.find(" ("), line.find(" - ")]
+                    .into_iter()
+                    .flatten()
+                    .min()
+                    .unwrap_or(line.len());
+                let typ = line[..end].trim().trim_end_matches(':').trim();
+                let value = Self::match_type(typ)
+                    .or_else(|| Self::match_sized(&format!("{typ}\n{}", details.join("\n"))))
+                    .or_else(|| typ.starts_with("[[Array]]").then_some(Self::ArrayUnknown))?;
+                alternatives.push((value, since));
+            }
+        } else {
+            let source = source.replace(" or ", ", ");
+            for part in source.split(", ") {
+                let (since, typ) = wiki::strip_since(part.trim());
+                let value = Self::match_type(&typ)?;
+                alternatives.push((value, since));
             }
         }
-        println!("unable to parse value: {source}");
-        Err("Unknown value".to_string())
+        alternatives.dedup();
+        match alternatives.len() {
+            0 => None,
+            1 => alternatives.pop().map(|(value, _)| value),
+            _ => Some(Self::OneOf(alternatives)),
+        }
     }
 
     #[must_use]
@@ -135,36 +318,26 @@ impl Value {
             "[[Array]] format [[Position#PositionASLW|PositionASLW]]"
             | "[[Position#PositionASLW|PositionASLW]]" => Some(Self::Position3DASLW),
             "[[Number]] in range 0..1" | "[[Number]] of control" => Some(Self::Number),
-            "[[Color|Color (RGBA)]]"
+            "[[Color|Color (RGB)]]"
             | "[[Array]] of [[Color|Color (RGB)]]"
             | "[[Array]] format [[Color|Color (RGB)]]"
+            | "[[Array]] in format [[Color|Color (RGB)]]" => Some(Self::ArrayColorRgb),
+            "[[Color|Color (RGBA)]]"
             | "[[Array]] of [[Color|Color (RGBA)]]"
             | "[[Array]] format [[Color|Color (RGBA)]]"
             | "[[Array]] in format [[Color|Color (RGBA)]]"
-            | "[[Array]] format [[Color|Color (RGBA)]] - text color" => Some(Self::ArrayColor),
-            "[[Array]] with [[Anything]]"
-            | "[[Array]] of [[Team Member]]s"
-            | "[[Array]] of [[Location]]s"
-            | "[[Array]] of [[Boolean]]s"
-            | "[[Array]] of [[Waypoint]]s"
-            | "[[Array]] of [[Group]]s"
-            | "[[Array]] of [[Object]]s"
-            | "[[Array]] - format [[Vector3D]]"
-            | "[[Array]] format [[Vector3D]]"
-            | "[[Array]] of [[Position]]s"
-            | "[[Array]] format [[Waypoint]]"
-            | "[[Array]] of [[String]] and/or [[Structured Text]]"
-            | "[[Array]] format [[ParticleArray]]"
-            | "[[Array]] of [[Number]]s, where each number represents index of currently active effect layer"
-            | "[[Array]] of [[Number]]s"
-            | "[[Array]] of [[Number]]s of any size"
-            | "[[Array]] of GUI [[Display]]s"
-            | "[[Array]] of [[Control]]s"
-            | "[[Array]] of [[String]]"
-            | "[[Array]] of string"
-            | "[[Array]] of [[String]]s"
-            | "[[Array]] of [[Number]]"
-            | "[[Array]] format [[Turret Path]]" => Some(Self::ArrayUnknown),
+            | "[[Array]] format [[Color|Color (RGBA)]] - text color" => Some(Self::ArrayColorRgba),
+            "[[Array]] format [year, month, day, hour, minute]"
+            | "[[Array]] in format [year, month, day, hour, minute]"
+            | "[[Array]] - [year, month, day, hour, minute]" => Some(Self::ArrayDate),
+            "[[Array]] - format [[Vector3D]]" | "[[Array]] format [[Vector3D]]" => {
+                Some(Self::Vector3d)
+            }
+            "[[Array]] format [[Waypoint]]" => Some(Self::Waypoint),
+            "[[Array]] format [[Turret Path]]" => Some(Self::TurretPath),
+            "[[Array]] with [[Anything]]" | "[[Array]] format [[ParticleArray]]" => {
+                Some(Self::ArrayUnknown)
+            }
             _ => None,
         }
     }
@@ -179,8 +352,10 @@ impl Value {
             "array" => Ok(Self::ArrayUnknown),
             "boolean" => Ok(Self::Boolean),
             "code" => Ok(Self::Code),
+            "color" => Ok(Self::ArrayColor),
             "config" => Ok(Self::Config),
             "control" => Ok(Self::Control),
+            "date" => Ok(Self::ArrayDate),
             "diary record" | "diaryrecord" => Ok(Self::DiaryRecord),
             "display" => Ok(Self::Display),
             "eden entity" | "edenentity" => Ok(Self::EdenEntity),
@@ -202,7 +377,7 @@ impl Value {
             "switch type" | "switchtype" => Ok(Self::SwitchType),
             "task" => Ok(Self::Task),
             "team member" | "teammember" => Ok(Self::TeamMember),
-            "turretpath" => Ok(Self::TurretPath),
+            "turret path" | "turretpath" => Ok(Self::TurretPath),
             "unitloadoutarray" => Ok(Self::UnitLoadoutArray),
             "position" => Ok(Self::Position),
             "position2d" => Ok(Self::Position2d),
@@ -222,6 +397,139 @@ impl Value {
     }
 }
 
+impl Value {
+    #[must_use]
+    /// Whether a value of this type is accepted where `target` is expected.
+    ///
+    /// `Anything` accepts every type, and a `OneOf` accepts any of its alternatives.
+    /// Every array-like type, including positions and colors, is an `ArrayUnknown`.
+    /// A sized array is accepted where a longer one is expected if the elements it leaves out are optional.
+    /// `Unknown` is accepted everywhere and accepts everything, since nothing is known about it.
+    /// Any `Code` is accepted where code with a signature is expected, as the signature only
+    /// describes how the code will be called.
+    pub fn is_assignable_to(&self, target: &Self) -> bool {
+        match (self, target) {
+            _ if self == target => true,
+            (Self::Unknown, _)
+            | (_, Self::Unknown | Self::Anything)
+            | (Self::Code, Self::CodeWithSignature(_)) => true,
+            (Self::OneOf(values), _) => values.iter().all(|(v, _)| v.is_assignable_to(target)),
+            (_, Self::OneOf(values)) => values.iter().any(|(v, _)| self.is_assignable_to(v)),
+            (Self::ArrayUnsized { typ, .. }, Self::ArrayUnsized { typ: target, .. }) => {
+                typ.is_assignable_to(target)
+            }
+            (Self::ArraySized { types, .. }, Self::ArrayUnsized { typ, .. }) => {
+                types.iter().all(|t| t.value.is_assignable_to(typ))
+            }
+            (Self::ArraySized { types, .. }, Self::ArraySized { types: target, .. }) => {
+                // optional trailing elements may be left out
+                types.len() <= target.len()
+                    && types
+                        .iter()
+                        .zip(target)
+                        .all(|(t, target)| t.value.is_assignable_to(&target.value))
+                    && target[types.len()..].iter().all(|t| t.optional)
+            }
+            _ => self
+                .supertype()
+                .is_some_and(|parent| parent.is_assignable_to(target)),
+        }
+    }
+
+    #[must_use]
+    /// The type without the alternatives and array elements that are newer than a version of a game.
+    ///
+    /// If no alternative is available, they are all kept.
+    pub fn at_version(&self, game: Game, version: &Version) -> Self {
+        let available = |since: &Option<Since>| {
+            since
+                .as_ref()
+                .is_none_or(|s| s.is_available_in(game, version))
+        };
+        match self {
+            Self::OneOf(values) => {
+                let mut values = values
+                    .iter()
+                    .filter(|(_, since)| available(since))
+                    .map(|(value, since)| (value.at_version(game, version), since.clone()))
+                    .collect::<Vec<_>>();
+                match values.len() {
+                    0 => self.clone(),
+                    1 => values.remove(0).0,
+                    _ => Self::OneOf(values),
+                }
+            }
+            Self::ArraySized { types, desc } => Self::ArraySized {
+                types: types
+                    .iter()
+                    .filter(|element| available(&element.since))
+                    .map(|element| ArraySizedElement {
+                        value: element.value.at_version(game, version),
+                        ..element.clone()
+                    })
+                    .collect(),
+                desc: desc.clone(),
+            },
+            Self::ArrayUnsized { typ, desc } => Self::ArrayUnsized {
+                typ: Box::new(typ.at_version(game, version)),
+                desc: desc.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    #[must_use]
+    /// The most specific type both values are assignable to.
+    pub fn common_supertype(&self, other: &Self) -> Self {
+        if other.is_assignable_to(self) {
+            return self.clone();
+        }
+        if self.is_assignable_to(other) {
+            return other.clone();
+        }
+        match (self, other) {
+            (Self::OneOf(values), _) => values
+                .iter()
+                .fold(other.clone(), |acc, (v, _)| acc.common_supertype(v)),
+            (_, Self::OneOf(_)) => other.common_supertype(self),
+            (Self::ArrayUnsized { typ, .. }, Self::ArrayUnsized { typ: other, .. }) => {
+                Self::ArrayUnsized {
+                    typ: Box::new(typ.common_supertype(other)),
+                    desc: String::new(),
+                }
+            }
+            _ => self
+                .supertype()
+                .map_or(Self::Anything, |parent| parent.common_supertype(other)),
+        }
+    }
+
+    /// The next more general type, if any.
+    const fn supertype(&self) -> Option<Self> {
+        match self {
+            Self::Position3dASL
+            | Self::Position3DASLW
+            | Self::Position3dATL
+            | Self::Position3dAGL
+            | Self::Position3dAGLS
+            | Self::Position3dRelative => Some(Self::Position3d),
+            Self::Position2d | Self::Position3d => Some(Self::Position),
+            Self::ArrayColorRgb | Self::ArrayColorRgba => Some(Self::ArrayColor),
+            Self::ArraySized { .. }
+            | Self::ArrayUnsized { .. }
+            | Self::ArrayDate
+            | Self::ArrayColor
+            | Self::Position
+            | Self::Vector3d
+            | Self::TurretPath
+            | Self::UnitLoadoutArray => Some(Self::ArrayUnknown),
+            Self::HashMapKnownKeys(_) => Some(Self::HashMapUnknown),
+            Self::CodeWithSignature(_) => Some(Self::Code),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -249,6 +557,7 @@ impl std::fmt::Display for Value {
                 Self::ArrayColorRgba => "Array Color RGBA".to_string(),
                 Self::Boolean => "Boolean".to_string(),
                 Self::Code => "Code".to_string(),
+                Self::CodeWithSignature(signature) => format!("Code {signature}"),
                 Self::Config => "Config".to_string(),
                 Self::Control => "Control".to_string(),
                 Self::DiaryRecord => "Diary Record".to_string(),
@@ -306,11 +615,11 @@ impl std::fmt::Display for Value {
 }
 
 #[cfg(test)]
-#[cfg(feature = "wiki")]
 mod tests {
-    use crate::model::Value;
+    use crate::model::{ArraySizedElement, Game, Since, Value, Version};
 
     #[test]
+    #[cfg(feature = "wiki")]
     fn single_values() {
         assert_eq!(Value::from_wiki("[[Anything]]"), Ok(Value::Anything));
         assert_eq!(Value::from_wiki("[[Boolean]]"), Ok(Value::Boolean));
@@ -327,4 +636,260 @@ mod tests {
             Ok(Value::ArrayUnknown)
         );
     }
+
+    #[test]
+    #[cfg(feature = "wiki")]
+    fn one_of() {
+        let mut since = Since::default();
+        since.set_arma_3(Some(Version::new(2, 4)));
+        assert_eq!(
+            Value::from_wiki("[[Boolean]] or {{GVI|arma3|2.04|size= 0.75}} [[Number]]"),
+            Ok(Value::OneOf(vec![
+                (Value::Boolean, None),
+                (Value::Number, Some(since))
+            ]))
+        );
+        assert_eq!(
+            Value::from_wiki("[[String]] or [[String]]"),
+            Ok(Value::String)
+        );
+        assert!(Value::from_wiki("[[Number]] or a distance").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "wiki")]
+    fn sized_arrays() {
+        let Ok(Value::ArraySized { types, desc }) = Value::from_wiki(
+            "[[Array]] format [position, radius] - the area\n* position: [[Position#PositionAGL|PositionAGL]] - centre of the area\n* '''radius''': [[Number]] - (Optional, default 50) radius in meters",
+        ) else {
+            panic!("expected a sized array");
+        };
+        assert_eq!(desc, "the area");
+        assert_eq!(
+            types,
+            [
+                ArraySizedElement {
+                    name: "position".to_string(),
+                    value: Value::Position3dAGL,
+                    desc: "centre of the area".to_string(),
+                    since: None,
+                    optional: false,
+                    default: None,
+                    string_kind: None,
+                },
+                ArraySizedElement {
+                    name: "radius".to_string(),
+                    value: Value::Number,
+                    desc: "radius in meters".to_string(),
+                    since: None,
+                    optional: true,
+                    default: Some("50".to_string()),
+                    string_kind: None,
+                }
+            ]
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] in format [x,y,z]"),
+            Err("Unknown value".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "wiki")]
+    fn unsized_arrays() {
+        let array_of = |typ: Value, desc: &str| Value::ArrayUnsized {
+            typ: Box::new(typ),
+            desc: desc.to_string(),
+        };
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Object]]s"),
+            Ok(array_of(Value::Object, ""))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Team Member]]s"),
+            Ok(array_of(Value::TeamMember, ""))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of GUI [[Display]]s"),
+            Ok(array_of(Value::Display, ""))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of string"),
+            Ok(array_of(Value::String, ""))
+        );
+        assert_eq!(
+            Value::from_wiki(
+                "[[Array]] of [[Number]]s, where each number represents index of currently active effect layer"
+            ),
+            Ok(array_of(
+                Value::Number,
+                "where each number represents index of currently active effect layer"
+            ))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Array]]s of [[Position#PositionASL|PositionASL]]"),
+            Ok(array_of(array_of(Value::Position3dASL, ""), ""))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[String]] and/or [[Structured Text]]"),
+            Ok(array_of(
+                Value::OneOf(vec![(Value::String, None), (Value::StructuredText, None)]),
+                ""
+            ))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Color|Color (RGB)]]"),
+            Ok(Value::ArrayColorRgb)
+        );
+        assert_eq!(
+            Value::from_wiki("[[Color|Color (RGBA)]]"),
+            Ok(Value::ArrayColorRgba)
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] format [year, month, day, hour, minute]"),
+            Ok(Value::ArrayDate)
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Object]]s - units"),
+            Ok(array_of(Value::Object, "units"))
+        );
+        assert_eq!(
+            Value::from_wiki("[[Array]] of [[Number]]s or [[String]]s"),
+            Ok(array_of(
+                Value::OneOf(vec![(Value::Number, None), (Value::String, None)]),
+                ""
+            ))
+        );
+        assert!(Value::from_wiki("[[Array]] of things").is_err());
+    }
+
+    fn array_of(typ: Value) -> Value {
+        Value::ArrayUnsized {
+            typ: Box::new(typ),
+            desc: String::new(),
+        }
+    }
+
+    #[test]
+    fn assignable() {
+        assert!(Value::Position3dASL.is_assignable_to(&Value::Position));
+        assert!(Value::Position3dASL.is_assignable_to(&Value::ArrayUnknown));
+        assert!(!Value::Position3dASL.is_assignable_to(&Value::Position3dATL));
+        assert!(!Value::ArrayUnknown.is_assignable_to(&Value::Position));
+        assert!(Value::ArrayColorRgba.is_assignable_to(&Value::ArrayColor));
+        assert!(Value::Object.is_assignable_to(&Value::Anything));
+        assert!(!Value::Anything.is_assignable_to(&Value::Object));
+        assert!(Value::Unknown.is_assignable_to(&Value::Object));
+        assert!(!Value::Number.is_assignable_to(&Value::String));
+
+        let handler = Value::CodeWithSignature(Box::default());
+        assert!(Value::Code.is_assignable_to(&handler));
+        assert!(handler.is_assignable_to(&Value::Code));
+        assert!(!Value::Code.is_assignable_to(&Value::String));
+
+        let one_of = Value::OneOf(vec![(Value::Number, None), (Value::Object, None)]);
+        assert!(Value::Number.is_assignable_to(&one_of));
+        assert!(!Value::String.is_assignable_to(&one_of));
+        assert!(!one_of.is_assignable_to(&Value::Number));
+        assert!(one_of.is_assignable_to(&Value::OneOf(vec![
+            (Value::Object, None),
+            (Value::Number, None),
+            (Value::String, None),
+        ])));
+
+        let element = ArraySizedElement {
+            name: "x".to_string(),
+            value: Value::Number,
+            desc: String::new(),
+            since: None,
+            optional: false,
+            default: None,
+            string_kind: None,
+        };
+        let pair = Value::ArraySized {
+            types: vec![element.clone(); 2],
+            desc: String::new(),
+        };
+        assert!(pair.is_assignable_to(&array_of(Value::Number)));
+        assert!(!pair.is_assignable_to(&array_of(Value::String)));
+        assert!(array_of(Value::Position3dAGL).is_assignable_to(&array_of(Value::Position)));
+
+        let optional = Value::ArraySized {
+            types: vec![
+                element.clone(),
+                ArraySizedElement {
+                    optional: true,
+                    ..element.clone()
+                },
+            ],
+            desc: String::new(),
+        };
+        let single = Value::ArraySized {
+            types: vec![element],
+            desc: String::new(),
+        };
+        assert!(pair.is_assignable_to(&optional));
+        assert!(single.is_assignable_to(&optional));
+        assert!(!single.is_assignable_to(&pair));
+        assert!(!optional.is_assignable_to(&single));
+    }
+
+    #[test]
+    fn at_version() {
+        let since = |minor| {
+            let mut since = Since::default();
+            since.set_arma_3(Some(Version::new(2, minor)));
+            Some(since)
+        };
+        let value = Value::OneOf(vec![
+            (Value::Number, None),
+            (Value::Boolean, since(4)),
+            (Value::String, since(18)),
+        ]);
+        assert_eq!(
+            value.at_version(Game::Arma3, &Version::new(2, 0)),
+            Value::Number
+        );
+        assert_eq!(
+            value.at_version(Game::Arma3, &Version::new(2, 10)),
+            Value::OneOf(vec![(Value::Number, None), (Value::Boolean, since(4))])
+        );
+        assert_eq!(value.at_version(Game::Arma3, &Version::new(2, 18)), value);
+        assert_eq!(
+            value.at_version(Game::Arma2, &Version::new(1, 0)),
+            Value::Number
+        );
+    }
+
+    #[test]
+    fn common_supertype() {
+        assert_eq!(
+            Value::Position3dASL.common_supertype(&Value::Position3dATL),
+            Value::Position3d
+        );
+        assert_eq!(
+            Value::Position2d.common_supertype(&Value::Position3dAGL),
+            Value::Position
+        );
+        assert_eq!(
+            Value::Vector3d.common_supertype(&Value::ArrayColor),
+            Value::ArrayUnknown
+        );
+        assert_eq!(
+            Value::Number.common_supertype(&Value::String),
+            Value::Anything
+        );
+        assert_eq!(
+            array_of(Value::Position3dASL).common_supertype(&array_of(Value::Position2d)),
+            array_of(Value::Position)
+        );
+        assert_eq!(
+            Value::OneOf(vec![
+                (Value::Position2d, None),
+                (Value::Position3dATL, None)
+            ])
+            .common_supertype(&Value::Position3d),
+            Value::Position
+        );
+    }
 }
@@ -1,15 +1,35 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// A game version, such as `2.18`, or a full build such as `2.18.152302`.
+///
+/// A version without a build sorts before every build of it.
 pub struct Version {
     major: u8,
     minor: u8,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    build: Option<u32>,
 }
 
 impl Version {
     #[must_use]
     pub const fn new(major: u8, minor: u8) -> Self {
-        Self { major, minor }
+        Self {
+            major,
+            minor,
+            build: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_build(self, build: u32) -> Self {
+        Self {
+            build: Some(build),
+            ..self
+        }
     }
 
     /// Parses a version string from the wiki.
@@ -20,16 +40,7 @@ impl Version {
         if source.is_empty() {
             return Ok(Self::new(0, 0));
         }
-        let Some((major, minor)) = source.split_once('.') else {
-            return Err(format!("Invalid version: {source}"));
-        };
-        let Ok(major) = major.trim().parse::<u8>() else {
-            return Err(format!("Invalid version: {source}"));
-        };
-        let Ok(minor) = minor.trim().parse::<u8>() else {
-            return Err(format!("Invalid version: {source}"));
-        };
-        Ok(Self { major, minor })
+        source.parse()
     }
 
     /// Parses a version string from the icon.
@@ -44,10 +55,10 @@ impl Version {
         let Some((game, source)) = source.split_once('|') else {
             return Err(format!("Invalid version: {source}"));
         };
-        let Some((version, _)) = source.split_once('|') else {
+        let Some(end) = source.find(['|', '}']) else {
             return Err(format!("Invalid version: {source}"));
         };
-        Ok((game.to_string(), Self::from_wiki(version)?))
+        Ok((game.to_string(), Self::from_wiki(&source[..end])?))
     }
 
     #[must_use]
@@ -59,22 +70,56 @@ impl Version {
     pub const fn minor(&self) -> u8 {
         self.minor
     }
+
+    #[must_use]
+    pub const fn build(&self) -> Option<u32> {
+        self.build
+    }
+
+    #[must_use]
+    /// The version without its build, as the wiki documents it.
+    pub const fn without_build(&self) -> Self {
+        Self::new(self.major, self.minor)
+    }
 }
 
-impl std::fmt::Display for Version {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        // Pad the minor version with a zero if it's a single digit.
-        write!(f, "{}.{:02}", self.major, self.minor)
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid version: {s}");
+        let mut parts = s.split('.').map(str::trim);
+        let major = parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(invalid)?;
+        let minor = parts
+            .next()
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(invalid)?;
+        let build = parts
+            .next()
+            .map(|p| p.parse().map_err(|_| invalid()))
+            .transpose()?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            major,
+            minor,
+            build,
+        })
     }
 }
 
-impl std::cmp::PartialOrd for Version {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if self.major == other.major {
-            self.minor.partial_cmp(&other.minor)
-        } else {
-            self.major.partial_cmp(&other.major)
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // Pad the minor version with a zero if it's a single digit.
+        write!(f, "{}.{:02}", self.major, self.minor)?;
+        if let Some(build) = self.build {
+            write!(f, ".{build}")?;
         }
+        Ok(())
     }
 }
 
@@ -88,6 +133,44 @@ mod tests {
         assert_eq!(Version::from_wiki(""), Ok(Version::new(0, 0)));
         assert_eq!(Version::from_wiki("1.00"), Ok(Version::new(1, 0)));
         assert_eq!(Version::from_wiki("1.0"), Ok(Version::new(1, 0)));
+        assert_eq!(
+            Version::from_wiki("2.18.152302"),
+            Ok(Version::new(2, 18).with_build(152_302))
+        );
+        assert!(Version::from_wiki("2").is_err());
+        assert!(Version::from_wiki("2.18.dev").is_err());
+        assert!(Version::from_wiki("2.18.1.1").is_err());
+    }
+
+    #[test]
+    fn ord() {
+        let mut versions = [
+            Version::new(2, 18).with_build(152_302),
+            Version::new(2, 2),
+            Version::new(2, 18),
+            Version::new(2, 18).with_build(151_000),
+            Version::new(1, 98),
+        ];
+        versions.sort();
+        assert_eq!(
+            versions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["1.98", "2.02", "2.18", "2.18.151000", "2.18.152302"]
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let version: Version = serde_yaml::from_str("major: 2\nminor: 18\n").unwrap();
+        assert_eq!(version, Version::new(2, 18));
+        assert_eq!(
+            serde_yaml::to_string(&version).unwrap(),
+            "major: 2\nminor: 18\n"
+        );
+        let build = Version::new(2, 18).with_build(152_302);
+        assert_eq!(
+            serde_yaml::from_str::<Version>(&serde_yaml::to_string(&build).unwrap()).unwrap(),
+            build
+        );
     }
 
     #[test]
@@ -95,5 +178,9 @@ mod tests {
         assert_eq!(Version::new(1, 0).to_string(), "1.00");
         assert_eq!(Version::new(1, 1).to_string(), "1.01");
         assert_eq!(Version::new(1, 16).to_string(), "1.16");
+        assert_eq!(
+            Version::new(2, 18).with_build(152_302).to_string(),
+            "2.18.152302"
+        );
     }
 }
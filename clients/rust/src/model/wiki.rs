@@ -0,0 +1,59 @@
+//! Helpers for the markup shared by the wiki parsers.
+
+use super::{Game, Since, Version};
+
+/// Splits a bullet list into its top level items, each with its nested lines.
+///
+/// One level of `*` is removed from the nested lines, so they form a bullet list of their own.
+pub fn bullets(source: &str) -> Vec<(&str, Vec<String>)> {
+    let mut items: Vec<(&str, Vec<String>)> = Vec::new();
+    for line in source.lines() {
+        let Some(line) = line.trim_start().strip_prefix('*') else {
+            continue;
+        };
+        if line.starts_with('*') {
+            if let Some((_, details)) = items.last_mut() {
+                details.push(line.to_string());
+            }
+        } else {
+            items.push((line.trim(), Vec::new()));
+        }
+    }
+    items
+}
+
+/// Removes a leading `{{GVI|..}}` icon or a `(since {{GVI|..}})` / `(before {{GVI|..}})`
+/// qualifier, returning the version it introduces or, for `before`, the version it was replaced in.
+pub fn strip_since(source: &str) -> (Option<Since>, String) {
+    let since_from = |icon: &str, before: bool| {
+        let (game, version) = Version::from_wiki_icon(icon).ok()?;
+        let mut since = Since::default();
+        if before {
+            since.set_until(Some((Game::from_wiki_key(&game)?, version)));
+        } else {
+            since.set_version(&game, version).ok()?;
+        }
+        Some(since)
+    };
+    if source.starts_with("{{GVI|")
+        && let Some(end) = source.find("}}").map(|end| end + 2)
+    {
+        return (
+            since_from(&source[..end], false),
+            source[end..].trim().to_string(),
+        );
+    }
+    for (qualifier, before) in [("(since {{GVI|", false), ("(before {{GVI|", true)] {
+        let Some(start) = source.find(qualifier) else {
+            continue;
+        };
+        let Some(end) = source[start..].find("}})").map(|end| start + end + 3) else {
+            continue;
+        };
+        let since = since_from(&source[start + 1..end - 1], before);
+        let mut rest = source.to_string();
+        rest.replace_range(start..end, "");
+        return (since, rest.replace("  ", " ").trim().to_string());
+    }
+    (None, source.to_string())
+}
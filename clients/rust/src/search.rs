@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::Commands,
+    event_handlers::EventHandlers,
+    model::{Command, EventHandlerNamespace, ParsedEventHandler},
+};
+
+const NAME_WEIGHT: f64 = 4.0;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+const NOTE_WEIGHT: f64 = 0.5;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "is", "it", "of",
+    "on", "or", "that", "the", "this", "to", "will", "with",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A document in the search index.
+pub enum SearchTarget {
+    /// A command, by its lowercase name.
+    Command(String),
+    EventHandler(EventHandlerNamespace, String),
+}
+
+#[derive(Clone, Copy, Debug)]
+/// A result of [`crate::Wiki::search`].
+pub struct SearchHit<'a> {
+    pub item: SearchItem<'a>,
+    pub score: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SearchItem<'a> {
+    Command(&'a Command),
+    EventHandler(EventHandlerNamespace, &'a ParsedEventHandler),
+}
+
+struct Posting {
+    doc: usize,
+    weight: f64,
+}
+
+/// An inverted index over the documentation of commands and event handlers.
+pub struct SearchIndex {
+    docs: Vec<(SearchTarget, f64)>,
+    terms: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    #[must_use]
+    pub fn new(commands: &Commands, event_handlers: &EventHandlers) -> Self {
+        let mut index = Self {
+            docs: Vec::new(),
+            terms: HashMap::new(),
+        };
+        for (name, command) in commands.iter() {
+            let mut fields = vec![
+                (command.name(), NAME_WEIGHT),
+                (command.description(), DESCRIPTION_WEIGHT),
+            ];
+            fields.extend(command.alias().iter().map(|a| (a.as_str(), NAME_WEIGHT)));
+            fields.extend(command.multiplayer_note().map(|n| (n, NOTE_WEIGHT)));
+            fields.extend(
+                command
+                    .problem_notes()
+                    .iter()
+                    .map(|n| (n.as_str(), NOTE_WEIGHT)),
+            );
+            fields.extend(command.examples().iter().map(|e| (e.as_str(), NOTE_WEIGHT)));
+            index.add(SearchTarget::Command(name.clone()), &fields);
+        }
+        for (ns, handlers) in event_handlers.iter() {
+            for handler in handlers {
+                index.add(
+                    SearchTarget::EventHandler(ns, handler.id().to_string()),
+                    &[
+                        (handler.id(), NAME_WEIGHT),
+                        (handler.description(), DESCRIPTION_WEIGHT),
+                    ],
+                );
+            }
+        }
+        index
+    }
+
+    fn add(&mut self, target: SearchTarget, fields: &[(&str, f64)]) {
+        let doc = self.docs.len();
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        let mut length = 0.0;
+        for (text, weight) in fields {
+            for term in tokenize(text) {
+                *weights.entry(term).or_default() += weight;
+                length += 1.0;
+            }
+        }
+        for (term, weight) in weights {
+            self.terms
+                .entry(term)
+                .or_default()
+                .push(Posting { doc, weight });
+        }
+        self.docs.push((target, f64::sqrt(length).max(1.0)));
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    /// Ranks the documents matching any term of the query, best first.
+    ///
+    /// Terms are weighted by how rare they are, and documents matching more of the terms rank higher.
+    pub fn search(&self, query: &str) -> Vec<(&SearchTarget, f64)> {
+        let mut terms = tokenize(query).collect::<Vec<_>>();
+        terms.sort();
+        terms.dedup();
+        let mut scores: HashMap<usize, (f64, usize)> = HashMap::new();
+        for term in &terms {
+            let Some(postings) = self.terms.get(term) else {
+                continue;
+            };
+            let idf = (self.docs.len() as f64 / postings.len() as f64).ln_1p();
+            for posting in postings {
+                let score = scores.entry(posting.doc).or_default();
+                score.0 += posting.weight / self.docs[posting.doc].1 * idf;
+                score.1 += 1;
+            }
+        }
+        let mut hits = scores
+            .into_iter()
+            .map(|(doc, (score, matched))| {
+                (
+                    &self.docs[doc].0,
+                    score * matched as f64 / terms.len() as f64,
+                )
+            })
+            .collect::<Vec<_>>();
+        // equal scores are ordered by name, rather than the order of the hash map
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        hits
+    }
+}
+
+/// Splits text into lowercase words, also splitting `camelCase` words into their parts.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            let parts = camel_parts(word);
+            let whole = (parts.len() > 1).then(|| word.to_lowercase());
+            whole.into_iter().chain(parts)
+        })
+        .filter(|term| term.len() > 1 && !STOP_WORDS.contains(&term.as_str()))
+}
+
+fn camel_parts(word: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for c in word.chars() {
+        if (c.is_uppercase() && !current.chars().last().is_some_and(char::is_uppercase)) || c == '_'
+        {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current).to_lowercase());
+            }
+            if c == '_' {
+                continue;
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        parts.push(current.to_lowercase());
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchIndex, SearchTarget, tokenize};
+    use crate::{commands::Commands, event_handlers::EventHandlers, model::Command};
+
+    fn command(name: &str, description: &str) -> Command {
+        let mut command = Command::default();
+        command.set_name(name.to_string());
+        command.set_description(description.to_string());
+        command
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            tokenize("Sets [[overcast]] of the setDamage BIS_fnc_spawn").collect::<Vec<_>>(),
+            [
+                "sets",
+                "overcast",
+                "setdamage",
+                "set",
+                "damage",
+                "bis_fnc_spawn",
+                "bis",
+                "fnc",
+                "spawn"
+            ]
+        );
+    }
+
+    #[test]
+    fn search() {
+        let commands = Commands::new([
+            command(
+                "setRain",
+                "Sets rain density smoothly over the given transition time.",
+            ),
+            command("setFog", "Changes the fog smoothly within the given time."),
+            command("rain", "Returns the current rain density."),
+        ]);
+        let index = SearchIndex::new(&commands, &EventHandlers::new([]));
+        let hits = index.search("set rain density");
+        assert_eq!(hits[0].0, &SearchTarget::Command("setrain".to_string()));
+        assert_eq!(hits[1].0, &SearchTarget::Command("rain".to_string()));
+        assert!(index.search("helicopter").is_empty());
+    }
+
+    #[test]
+    fn ties() {
+        let names = ["radioB", "radioD", "radioA", "radioE", "radioC"];
+        let commands = Commands::new(names.map(|name| command(name, "Toggles the radio.")));
+        let index = SearchIndex::new(&commands, &EventHandlers::new([]));
+        let hits = index.search("toggles radio");
+        assert_eq!(
+            hits.iter()
+                .map(|(target, _)| match target {
+                    SearchTarget::Command(name) => name.as_str(),
+                    SearchTarget::EventHandler(_, id) => id.as_str(),
+                })
+                .collect::<Vec<_>>(),
+            ["radioa", "radiob", "radioc", "radiod", "radioe"]
+        );
+    }
+}
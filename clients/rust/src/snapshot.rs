@@ -0,0 +1,18 @@
+//! The binary snapshot of the dist data, written by the build script and decoded by [`crate::Wiki::load_dist`].
+//!
+//! The model relies on `skip_serializing_if` and untagged enums,
+//! so the snapshot uses `MessagePack` with named fields rather than a positional format.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Command, EventHandlerNamespace, ParsedEventHandler, Version};
+
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: Version,
+    pub commands: Vec<Command>,
+    pub event_handlers: Vec<(EventHandlerNamespace, Vec<ParsedEventHandler>)>,
+    /// Files left out because they could not be read, by path within the dist tree, with the reason.
+    #[serde(default)]
+    pub skipped: Vec<(String, String)>,
+}
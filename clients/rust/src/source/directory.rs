@@ -0,0 +1,61 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+use crate::error::WikiError;
+
+use super::WikiSource;
+
+/// Loads the wiki from a local directory, such as a checkout of the `dist` branch.
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl WikiSource for DirectorySource {
+    fn files(&self) -> Result<Vec<String>, WikiError> {
+        let mut files = Vec::new();
+        walk(&self.root, "", &mut files)?;
+        Ok(files)
+    }
+
+    fn read(&self, path: &str) -> Result<Cow<'_, [u8]>, WikiError> {
+        let path = self.root.join(path);
+        if !path.exists() {
+            return Err(WikiError::Missing(path));
+        }
+        std::fs::read(&path)
+            .map(Cow::Owned)
+            .map_err(|e| WikiError::io(&path, e))
+    }
+}
+
+fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), WikiError> {
+    for entry in std::fs::read_dir(dir).map_err(|e| WikiError::io(dir, e))? {
+        let entry = entry.map_err(|e| WikiError::io(dir, e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        // skip `.git` and other hidden entries
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, &format!("{prefix}{name}/"), files)?;
+        } else {
+            files.push(format!("{prefix}{name}"));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use std::borrow::Cow;
+
+use rust_embed::RustEmbed;
+
+use crate::error::WikiError;
+
+use super::WikiSource;
+
+#[derive(RustEmbed)]
+#[folder = "$OUT_DIR/arma3-wiki"]
+struct Asset;
+
+/// Loads the wiki from the YAML files embedded at build time.
+///
+/// [`crate::Wiki::load_dist`] decodes the binary snapshot of the same files instead,
+/// this is kept behind the `yaml` feature to compare the two.
+pub struct EmbeddedSource;
+
+impl WikiSource for EmbeddedSource {
+    fn files(&self) -> Result<Vec<String>, WikiError> {
+        Ok(Asset::iter().map(|path| path.to_string()).collect())
+    }
+
+    fn read(&self, path: &str) -> Result<Cow<'_, [u8]>, WikiError> {
+        Asset::get(path)
+            .map(|file| file.data)
+            .ok_or_else(|| WikiError::Missing(path.into()))
+    }
+}
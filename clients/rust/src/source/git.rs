@@ -0,0 +1,197 @@
+use std::{borrow::Cow, fs::File, io::Write, path::Path, path::PathBuf, time::SystemTime};
+
+use git2::Repository;
+
+use crate::{error::WikiError, loader::WikiLoader};
+
+use super::{DirectorySource, WikiSource};
+
+/// Loads the wiki from a local clone of the `dist` branch, cloning or updating it as needed.
+pub struct GitSource {
+    dir: DirectorySource,
+    updated: bool,
+}
+
+impl GitSource {
+    /// Opens the default clone, updating it if it has not been updated recently or `force_pull` is set.
+    ///
+    /// Use [`WikiLoader`] to configure the remote, branch, revision or cache directory.
+    ///
+    /// # Errors
+    /// Returns an error if the repository could not be cloned.
+    pub fn open(force_pull: bool) -> Result<Self, WikiError> {
+        WikiLoader::new().force_pull(force_pull).open_git()
+    }
+
+    /// Opens the clone described by the loader.
+    ///
+    /// Failing to fetch is not an error, the cached files are used instead,
+    /// unless a pinned revision is not available locally, or a previously pinned revision
+    /// is checked out and the branch is not available locally.
+    pub(crate) fn sync(loader: &WikiLoader) -> Result<Self, WikiError> {
+        let cache = match loader.get_cache_dir() {
+            Some(dir) => {
+                std::fs::create_dir_all(dir).map_err(|e| WikiError::io(dir, e))?;
+                dir.to_path_buf()
+            }
+            None => get_appdata()?,
+        };
+        let repo = if let Ok(repo) = Repository::open(&cache) {
+            if repo
+                .find_remote("origin")
+                .is_ok_and(|r| r.url() != Some(loader.get_url()))
+            {
+                repo.remote_set_url("origin", loader.get_url())
+                    .map_err(WikiError::git("Failed to set remote"))?;
+            }
+            repo
+        } else {
+            git2::build::RepoBuilder::new()
+                .branch(loader.get_branch())
+                .clone(loader.get_url(), &cache)
+                .map_err(WikiError::git("Failed to clone repository"))?
+        };
+        let updated = if let Some(revision) = loader.get_revision() {
+            Self::checkout_revision(&repo, loader, revision)?
+        } else if !loader.get_force_pull()
+            && Self::on_branch(&repo, loader.get_branch())
+            && Self::recently_updated(&cache, loader.get_refresh_interval().as_secs())
+        {
+            false
+        } else {
+            match Self::update(&repo, &cache, loader.get_branch()) {
+                Ok(()) => true,
+                // offline, use the cached branch, but never a previously pinned revision
+                Err(_) if Self::on_branch(&repo, loader.get_branch()) => false,
+                Err(error) => {
+                    Self::checkout_local(&repo, loader.get_branch()).map_err(|_| error)?;
+                    false
+                }
+            }
+        };
+        Ok(Self {
+            dir: DirectorySource::new(cache),
+            updated,
+        })
+    }
+
+    /// Checks out a pinned revision, only fetching if it is not already available.
+    fn checkout_revision(
+        repo: &Repository,
+        loader: &WikiLoader,
+        revision: &str,
+    ) -> Result<bool, WikiError> {
+        let fetched = if repo.revparse_single(revision).is_err() || loader.get_force_pull() {
+            repo.find_remote("origin")
+                .and_then(|mut r| {
+                    r.fetch(
+                        &[loader.get_branch(), "+refs/tags/*:refs/tags/*"],
+                        None,
+                        None,
+                    )
+                })
+                .is_ok()
+        } else {
+            false
+        };
+        let commit = repo
+            .revparse_single(revision)
+            .and_then(|o| o.peel_to_commit())
+            .map_err(WikiError::git("Failed to find revision"))?;
+        if repo.head().ok().and_then(|h| h.target()) != Some(commit.id()) {
+            repo.checkout_tree(
+                commit.as_object(),
+                Some(git2::build::CheckoutBuilder::default().force()),
+            )
+            .map_err(WikiError::git("Failed to checkout revision"))?;
+            repo.set_head_detached(commit.id())
+                .map_err(WikiError::git("Failed to set HEAD"))?;
+            return Ok(true);
+        }
+        Ok(fetched)
+    }
+
+    fn update(repo: &Repository, cache: &Path, branch: &str) -> Result<(), WikiError> {
+        repo.find_remote("origin")
+            .and_then(|mut r| r.fetch(&[branch], None, None))
+            .map_err(WikiError::git("Failed to fetch remote"))?;
+        let fetch_head = repo
+            .find_reference("FETCH_HEAD")
+            .map_err(WikiError::git("Failed to find FETCH_HEAD"))?;
+        let commit = repo
+            .reference_to_annotated_commit(&fetch_head)
+            .map_err(WikiError::git("Failed to find FETCH_HEAD"))?;
+        let analysis = repo
+            .merge_analysis(&[&commit])
+            .map_err(WikiError::git("Failed to analyze merge"))?;
+        let refname = format!("refs/heads/{branch}");
+        if !Self::on_branch(repo, branch)
+            || (!analysis.0.is_up_to_date() && analysis.0.is_fast_forward())
+        {
+            repo.reference(&refname, commit.id(), true, "Fast-Forward")
+                .map_err(WikiError::git("Failed to set reference"))?;
+            repo.set_head(&refname)
+                .map_err(WikiError::git("Failed to set HEAD"))?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+                .map_err(WikiError::git("Failed to checkout HEAD"))?;
+        }
+        let Ok(mut file) = File::create(cache.join("last-update.timestamp")) else {
+            return Ok(());
+        };
+        if let Ok(now) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            let _ = file.write_all(now.as_secs().to_string().as_bytes());
+        }
+        Ok(())
+    }
+
+    /// Attaches HEAD to the local copy of the branch, without fetching.
+    fn checkout_local(repo: &Repository, branch: &str) -> Result<(), WikiError> {
+        let refname = format!("refs/heads/{branch}");
+        repo.find_reference(&refname)
+            .map_err(WikiError::git("Failed to find branch"))?;
+        repo.set_head(&refname)
+            .map_err(WikiError::git("Failed to set HEAD"))?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .map_err(WikiError::git("Failed to checkout HEAD"))
+    }
+
+    /// Whether HEAD is attached to the branch, and not a previously pinned revision.
+    fn on_branch(repo: &Repository, branch: &str) -> bool {
+        repo.head()
+            .is_ok_and(|h| h.name() == Some(&format!("refs/heads/{branch}")))
+    }
+
+    fn recently_updated(path: &Path, interval: u64) -> bool {
+        if let Ok(timestamp) = std::fs::read_to_string(path.join("last-update.timestamp"))
+            && let Ok(timestamp) = timestamp.parse::<u64>()
+            && let Ok(elapsed) = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|t| t.as_secs())
+        {
+            return elapsed.saturating_sub(timestamp) < interval;
+        }
+        false
+    }
+}
+
+impl WikiSource for GitSource {
+    fn files(&self) -> Result<Vec<String>, WikiError> {
+        self.dir.files()
+    }
+
+    fn read(&self, path: &str) -> Result<Cow<'_, [u8]>, WikiError> {
+        self.dir.read(path)
+    }
+
+    fn updated(&self) -> bool {
+        self.updated
+    }
+}
+
+fn get_appdata() -> Result<PathBuf, WikiError> {
+    let dirs = directories::ProjectDirs::from("org", "acemod", "arma3-wiki")
+        .ok_or(WikiError::NoCacheDir)?;
+    let appdata = dirs.data_local_dir();
+    std::fs::create_dir_all(appdata).map_err(|e| WikiError::io(appdata, e))?;
+    Ok(appdata.to_path_buf())
+}
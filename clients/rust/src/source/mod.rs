@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+
+use crate::error::WikiError;
+
+mod directory;
+#[cfg(feature = "yaml")]
+mod embedded;
+#[cfg(feature = "remote")]
+mod git;
+
+pub use directory::DirectorySource;
+#[cfg(feature = "yaml")]
+pub use embedded::EmbeddedSource;
+#[cfg(feature = "remote")]
+pub use git::GitSource;
+
+/// A tree of wiki files, laid out like the `dist` branch.
+///
+/// ```text
+/// version.txt
+/// commands/<name>.yml
+/// events/<namespace>/<id>.yml
+/// ```
+pub trait WikiSource {
+    /// Lists every file in the source, relative to its root and separated by `/`.
+    ///
+    /// # Errors
+    /// Returns an error if the source could not be listed.
+    fn files(&self) -> Result<Vec<String>, WikiError>;
+
+    /// Reads a file, relative to the root of the source.
+    ///
+    /// # Errors
+    /// Returns an error if the file does not exist or could not be read.
+    fn read(&self, path: &str) -> Result<Cow<'_, [u8]>, WikiError>;
+
+    /// Whether the source was just updated.
+    fn updated(&self) -> bool {
+        false
+    }
+
+    /// Reads a file as UTF-8, relative to the root of the source.
+    ///
+    /// # Errors
+    /// Returns an error if the file could not be read or is not valid UTF-8.
+    fn read_to_string(&self, path: &str) -> Result<String, WikiError> {
+        let data = self.read(path)?;
+        std::str::from_utf8(&data)
+            .map(ToString::to_string)
+            .map_err(|source| WikiError::Utf8 {
+                path: path.into(),
+                source,
+            })
+    }
+}
@@ -0,0 +1,590 @@
+//! A lightweight reading of SQF source, enough to tell which commands it uses and how.
+
+use std::collections::HashSet;
+
+use crate::{
+    commands::Commands,
+    event_handlers::EventHandlers,
+    model::{
+        Arg, Binding, Call, Command, EventHandlerNamespace, Game, Param, ParsedEventHandler, Since,
+        Syntax, Version,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A command or variable name.
+    Identifier,
+    Number,
+    /// A string literal, including its quotes.
+    String,
+    /// Brackets, separators and operators.
+    Symbol,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// The byte offset of the token in the source.
+    pub offset: usize,
+}
+
+impl Token<'_> {
+    fn is(&self, symbol: &str) -> bool {
+        self.kind == TokenKind::Symbol && self.text == symbol
+    }
+
+    /// Whether the token can start an operand of a unary command.
+    fn starts_operand(&self) -> bool {
+        match self.kind {
+            TokenKind::Symbol => ["[", "{", "(", "!", "-", "+"].contains(&self.text),
+            _ => true,
+        }
+    }
+
+    /// The content of a string literal, without its quotes.
+    fn string(&self) -> Option<&str> {
+        if self.kind != TokenKind::String || self.text.len() < 2 {
+            return None;
+        }
+        let quote = &self.text[..1];
+        self.text
+            .strip_prefix(quote)
+            .and_then(|text| text.strip_suffix(quote))
+    }
+}
+
+const TWO_CHAR_SYMBOLS: &[&str] = &["==", "!=", ">=", "<=", "&&", "||", ">>"];
+
+/// Splits SQF source into tokens, skipping comments and preprocessor directives.
+pub fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line_start = true;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        if c == b'\n' {
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let directive = line_start && c == b'#';
+        line_start = false;
+        let kind = if directive {
+            // a directive ends at the first newline not escaped by a backslash
+            while i < bytes.len() && !(bytes[i] == b'\n' && bytes[i - 1] != b'\\') {
+                i += 1;
+            }
+            continue;
+        } else if source[i..].starts_with("//") {
+            i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            continue;
+        } else if source[i..].starts_with("/*") {
+            i = source[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |end| i + end + 4);
+            continue;
+        } else if c == b'"' || c == b'\'' {
+            i += 1;
+            while i < bytes.len() {
+                if bytes[i] == c {
+                    // a doubled quote is an escaped quote
+                    if bytes.get(i + 1) == Some(&c) {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                i += 1;
+            }
+            TokenKind::String
+        } else if c.is_ascii_digit()
+            || c == b'$'
+            || (c == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+        {
+            i += 1;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                i += 1;
+            }
+            TokenKind::Number
+        } else if source[i..].starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            i = source[i..]
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .map_or(bytes.len(), |end| i + end);
+            TokenKind::Identifier
+        } else {
+            i += if TWO_CHAR_SYMBOLS.iter().any(|s| source[i..].starts_with(s)) {
+                2
+            } else {
+                source[i..].chars().next().map_or(1, char::len_utf8)
+            };
+            TokenKind::Symbol
+        };
+        tokens.push(Token {
+            kind,
+            text: &source[start..i.min(bytes.len())],
+            offset: start,
+        });
+    }
+    tokens
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// What raised the version required by a piece of SQF.
+pub enum RequiredItem<'a> {
+    Command(&'a Command),
+    /// A syntax of a command, by its index.
+    Syntax(&'a Command, usize),
+    Param(&'a Command, &'a Param),
+    EventHandler(EventHandlerNamespace, &'a ParsedEventHandler),
+}
+
+impl std::fmt::Display for RequiredItem<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Command(command) => write!(f, "{}", command.name()),
+            Self::Syntax(command, index) => write!(f, "{} (syntax {})", command.name(), index + 1),
+            Self::Param(command, param) => {
+                write!(f, "{} (param `{}`)", command.name(), param.name())
+            }
+            Self::EventHandler(_, handler) => write!(f, "{} event handler", handler.id()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Requirement<'a> {
+    pub item: RequiredItem<'a>,
+    pub version: Version,
+    /// The byte offset of the command using the item in the source.
+    pub offset: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// The Arma 3 version required to run a piece of SQF, see [`crate::Wiki::required_version`].
+pub struct RequiredVersion<'a> {
+    requirements: Vec<Requirement<'a>>,
+}
+
+impl<'a> RequiredVersion<'a> {
+    #[must_use]
+    /// Finds the commands, syntaxes, params and event handlers used by the source that have a documented Arma 3 version.
+    ///
+    /// Commands are told apart as nular, unary or binary by their neighbouring tokens,
+    /// and the params of an array operand are only counted up to the length of the array literal.
+    pub fn new(commands: &'a Commands, event_handlers: &'a EventHandlers, source: &str) -> Self {
+        let tokens = tokenize(source);
+        let mut required = Self::default();
+        let mut seen = HashSet::new();
+        let mut add = |item: RequiredItem<'a>, since: Option<&Since>, offset| {
+            if let Some(version) = since.and_then(|since| since.version(Game::Arma3))
+                && seen.insert(item.to_string())
+            {
+                required.requirements.push(Requirement {
+                    item,
+                    version: *version,
+                    offset,
+                });
+            }
+        };
+        let mut after_operand = false;
+        for (i, token) in tokens.iter().enumerate() {
+            let command = match token.kind {
+                TokenKind::Number | TokenKind::String => None,
+                TokenKind::Symbol if [")", "]", "}"].contains(&token.text) => None,
+                TokenKind::Symbol if ["(", "[", "{", ",", ";", "="].contains(&token.text) => {
+                    after_operand = false;
+                    continue;
+                }
+                _ if token.text.starts_with('_') => None,
+                _ => commands.get(token.text),
+            };
+            let Some(command) = command else {
+                // operators without a command entry, such as `:` in `switch`, expect an operand next
+                after_operand =
+                    token.kind != TokenKind::Symbol || token.text.starts_with([')', ']', '}']);
+                continue;
+            };
+            add(
+                RequiredItem::Command(command),
+                Some(command.since()),
+                token.offset,
+            );
+
+            let next = tokens.get(i + 1);
+            let has = |kind: fn(&Call) -> bool| command.syntax().iter().any(|s| kind(s.call()));
+            let binary = after_operand && has(Call::is_binary);
+            let (left, right) = if binary {
+                (
+                    i.checked_sub(1)
+                        .and_then(|prev| array_before(&tokens, prev)),
+                    Some(operand_after(&tokens, i + 1)),
+                )
+            } else if has(Call::is_unary) && next.is_some_and(Token::starts_operand) {
+                (None, Some(operand_after(&tokens, i + 1)))
+            } else {
+                (None, None)
+            };
+            after_operand = right.is_none();
+
+            if let Some(index) = pick_syntax(command, binary, right) {
+                let syntax = &command.syntax()[index];
+                add(
+                    RequiredItem::Syntax(command, index),
+                    syntax.since(),
+                    token.offset,
+                );
+                let bindings = syntax.bindings();
+                for (binding, operand) in [(bindings.left(), left), (bindings.right(), right)] {
+                    for param in used_params(binding, operand) {
+                        add(
+                            RequiredItem::Param(command, param),
+                            param.since(),
+                            token.offset,
+                        );
+                    }
+                }
+            }
+
+            let id = match right {
+                Some(Operand::Array(_)) => tokens.get(i + 2).and_then(Token::string),
+                Some(Operand::Other) => next.and_then(Token::string),
+                None => None,
+            };
+            if let Some(id) = id {
+                let handler = EventHandlerNamespace::by_command(command.name())
+                    .into_iter()
+                    .filter_map(|ns| event_handlers.get(ns, id).map(|h| (ns, h)))
+                    .min_by_key(|(_, h)| h.since().and_then(|s| s.version(Game::Arma3)).copied());
+                if let Some((ns, handler)) = handler {
+                    add(
+                        RequiredItem::EventHandler(ns, handler),
+                        handler.since(),
+                        token.offset,
+                    );
+                }
+            }
+        }
+        required
+    }
+
+    #[must_use]
+    /// The minimum Arma 3 version, or `None` if nothing used has a documented version.
+    pub fn version(&self) -> Option<Version> {
+        self.requirements.iter().map(|r| r.version).max()
+    }
+
+    /// The items requiring the minimum version.
+    pub fn offending(&self) -> impl Iterator<Item = &Requirement<'a>> {
+        let version = self.version();
+        self.requirements
+            .iter()
+            .filter(move |r| Some(r.version) == version)
+    }
+
+    #[must_use]
+    /// Every item with a documented version, in the order they are used.
+    pub fn requirements(&self) -> &[Requirement<'a>] {
+        &self.requirements
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operand {
+    /// An array literal, by its number of elements.
+    Array(usize),
+    Other,
+}
+
+fn operand_after(tokens: &[Token], start: usize) -> Operand {
+    if tokens.get(start).is_some_and(|t| t.is("[")) {
+        array_len(tokens[start + 1..].iter()).map_or(Operand::Other, Operand::Array)
+    } else {
+        Operand::Other
+    }
+}
+
+/// The operand ending at `end`, for the left side of a binary command.
+fn array_before(tokens: &[Token], end: usize) -> Option<Operand> {
+    if !tokens[end].is("]") {
+        return Some(Operand::Other);
+    }
+    // walk back to the matching `[` and count from there
+    let mut depth = 0usize;
+    for start in (0..=end).rev() {
+        match tokens[start].text {
+            "]" | ")" | "}" if tokens[start].kind == TokenKind::Symbol => depth += 1,
+            "[" | "(" | "{" if tokens[start].kind == TokenKind::Symbol => {
+                depth -= 1;
+                if depth == 0 {
+                    return array_len(tokens[start + 1..].iter()).map(Operand::Array);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Counts the elements of an array literal, from the token after its `[`.
+fn array_len<'a>(tokens: impl Iterator<Item = &'a Token<'a>>) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut elements = 0;
+    let mut empty = true;
+    for token in tokens {
+        if token.kind == TokenKind::Symbol {
+            match token.text {
+                "[" | "(" | "{" => depth += 1,
+                "]" | ")" | "}" if depth == 0 => {
+                    return Some(if empty { 0 } else { elements + 1 });
+                }
+                "]" | ")" | "}" => depth -= 1,
+                "," if depth == 0 => elements += 1,
+                _ => {}
+            }
+        }
+        empty = false;
+    }
+    None
+}
+
+/// Picks the syntax used, preferring one with an array where an array literal is given,
+/// and then the oldest one.
+fn pick_syntax(command: &Command, binary: bool, right: Option<Operand>) -> Option<usize> {
+    let fits = |syntax: &Syntax, shape: bool| match (syntax.call(), right) {
+        (Call::Nular, None) => true,
+        (Call::Unary(arg), Some(operand)) if !binary => {
+            !shape || is_array(arg) == matches!(operand, Operand::Array(_))
+        }
+        (Call::Binary(_, arg), Some(operand)) if binary => {
+            !shape || is_array(arg) == matches!(operand, Operand::Array(_))
+        }
+        _ => false,
+    };
+    let version = |syntax: &Syntax| syntax.since().and_then(|s| s.version(Game::Arma3)).copied();
+    let oldest = |shape: bool| {
+        command
+            .syntax()
+            .iter()
+            .enumerate()
+            .filter(|(_, syntax)| fits(syntax, shape))
+            .min_by_key(|(_, syntax)| version(syntax))
+            .map(|(index, _)| index)
+    };
+    oldest(true).or_else(|| oldest(false))
+}
+
+const fn is_array(arg: &Arg) -> bool {
+    matches!(arg, Arg::Array(_))
+}
+
+/// The params given by an operand.
+fn used_params<'a>(binding: Option<&Binding<'a>>, operand: Option<Operand>) -> Vec<&'a Param> {
+    let Some(binding) = binding else {
+        return Vec::new();
+    };
+    match operand {
+        Some(Operand::Array(len)) => (0..len)
+            .filter_map(|index| binding.get(index, len).and_then(Binding::param))
+            .collect(),
+        Some(Operand::Other) => binding.param().into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RequiredItem, RequiredVersion, TokenKind, tokenize};
+    use crate::{
+        commands::Commands,
+        event_handlers::EventHandlers,
+        model::{
+            Arg, Call, Command, EventHandlerNamespace, Param, ParsedEventHandler, Since, Syntax,
+            Value, Version,
+        },
+    };
+
+    fn since(major: u8, minor: u8) -> Since {
+        let mut since = Since::default();
+        since.set_arma_3(Some(Version::new(major, minor)));
+        since
+    }
+
+    fn param(name: &str, since: Option<Since>) -> Param {
+        Param::new(name.to_string(), None, Value::Anything, false, None, since)
+    }
+
+    fn commands() -> Commands {
+        let mut player = Command::default();
+        player.set_name("player".to_string());
+        player.set_syntax(vec![Syntax::new(
+            Call::Nular,
+            (Value::Object, None),
+            Vec::new(),
+            None,
+            None,
+        )]);
+        *player.since_mut() = since(0, 50);
+
+        let mut set_damage = Command::default();
+        set_damage.set_name("setDamage".to_string());
+        set_damage.set_syntax(vec![
+            Syntax::new(
+                Call::Binary(
+                    Arg::Item("object".to_string()),
+                    Arg::Item("damage".to_string()),
+                ),
+                (Value::Nothing, None),
+                vec![param("object", None), param("damage", None)],
+                None,
+                None,
+            ),
+            Syntax::new(
+                Call::Binary(
+                    Arg::Item("object".to_string()),
+                    Call::parse_params("[damage, useEffects, killer]").unwrap(),
+                ),
+                (Value::Nothing, None),
+                vec![
+                    param("object", None),
+                    param("damage", None),
+                    param("useEffects", None),
+                    param("killer", Some(since(2, 14))),
+                ],
+                Some(since(1, 68)),
+                None,
+            ),
+        ]);
+        *set_damage.since_mut() = since(0, 50);
+
+        let mut add_event_handler = Command::default();
+        add_event_handler.set_name("addEventHandler".to_string());
+        add_event_handler.set_syntax(vec![Syntax::new(
+            Call::Binary(
+                Arg::Item("object".to_string()),
+                Call::parse_params("[type, function]").unwrap(),
+            ),
+            (Value::Number, None),
+            vec![
+                param("object", None),
+                param("type", None),
+                param("function", None),
+            ],
+            None,
+            None,
+        )]);
+
+        let mut hint = Command::default();
+        hint.set_name("hint".to_string());
+        hint.set_syntax(vec![Syntax::new(
+            Call::Unary(Arg::Item("text".to_string())),
+            (Value::Nothing, None),
+            vec![param("text", None)],
+            None,
+            None,
+        )]);
+        Commands::new([player, set_damage, add_event_handler, hint])
+    }
+
+    #[test]
+    fn tokens() {
+        let tokens = tokenize(
+            "#include \"a.hpp\"\n// comment\n_a = [1, \"it\"\"s\"] /* skip */ select 0x1F; hint str _a;",
+        );
+        assert_eq!(
+            tokens.iter().map(|t| t.text).collect::<Vec<_>>(),
+            [
+                "_a",
+                "=",
+                "[",
+                "1",
+                ",",
+                "\"it\"\"s\"",
+                "]",
+                "select",
+                "0x1F",
+                ";",
+                "hint",
+                "str",
+                "_a",
+                ";"
+            ]
+        );
+        assert_eq!(tokens[5].kind, TokenKind::String);
+        assert_eq!(tokens[8].kind, TokenKind::Number);
+        assert_eq!(tokens[10].offset, 70);
+
+        let tokens = tokenize("_héllo = player;");
+        assert_eq!(
+            tokens.iter().map(|t| t.text).collect::<Vec<_>>(),
+            ["_héllo", "=", "player", ";"]
+        );
+        assert_eq!(tokens[2].offset, 10);
+    }
+
+    #[test]
+    fn required_version() {
+        let commands = commands();
+        let handler: ParsedEventHandler = serde_yaml::from_str(
+            "id: Fired\ndescription: ''\nparams: []\nsince:\n  arma_3:\n    major: 2\n    minor: 6\nargument_loc: Global\neffect_loc: Local\n",
+        )
+        .unwrap();
+        let event_handlers = EventHandlers::new([(EventHandlerNamespace::Standard, vec![handler])]);
+
+        let required = RequiredVersion::new(&commands, &event_handlers, "player setDamage 1;");
+        assert_eq!(required.version(), Some(Version::new(0, 50)));
+
+        let required =
+            RequiredVersion::new(&commands, &event_handlers, "player setDamage [1, false];");
+        assert_eq!(required.version(), Some(Version::new(1, 68)));
+        assert!(matches!(
+            required.offending().next().unwrap().item,
+            RequiredItem::Syntax(c, 1) if c.name() == "setDamage"
+        ));
+
+        let required = RequiredVersion::new(
+            &commands,
+            &event_handlers,
+            "player setDamage [1, false, _killer];",
+        );
+        assert_eq!(required.version(), Some(Version::new(2, 14)));
+        assert_eq!(
+            required.offending().next().unwrap().item.to_string(),
+            "setDamage (param `killer`)"
+        );
+
+        let required = RequiredVersion::new(
+            &commands,
+            &event_handlers,
+            "player addEventHandler [\"Fired\", { hint \"fired\" }];",
+        );
+        let offending = required.offending().collect::<Vec<_>>();
+        assert_eq!(offending.len(), 1);
+        assert_eq!(offending[0].item.to_string(), "Fired event handler");
+        assert_eq!(offending[0].offset, 7);
+
+        assert_eq!(
+            RequiredVersion::new(&commands, &event_handlers, "hint \"hello\";").version(),
+            None
+        );
+
+        // an unterminated string while still typing
+        assert_eq!(
+            RequiredVersion::new(&commands, &event_handlers, "player setDamage \"").version(),
+            Some(Version::new(0, 50))
+        );
+        assert_eq!(
+            RequiredVersion::new(&commands, &event_handlers, "player addEventHandler \"Fired")
+                .version(),
+            Some(Version::new(0, 50))
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Case-insensitive keys, so lookups don't need to allocate a lowercase copy of the name.
+
+use std::hash::{Hash, Hasher};
+
+use hashbrown::Equivalent;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// An owned key, stored in lowercase.
+pub struct Key(String);
+
+impl Key {
+    pub fn new(name: &str) -> Self {
+        Self(name.to_ascii_lowercase())
+    }
+
+    pub const fn as_string(&self) -> &String {
+        &self.0
+    }
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Uncased(&self.0).hash(state);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// A borrowed name of any casing, used to look up a [`Key`].
+pub struct Uncased<'a>(pub &'a str);
+
+impl Uncased<'_> {
+    /// Compares against a key, in the order of the lowercase names.
+    pub fn cmp_key(self, key: &Key) -> std::cmp::Ordering {
+        key.0
+            .bytes()
+            .cmp(self.0.bytes().map(|b| b.to_ascii_lowercase()))
+            .reverse()
+    }
+
+    /// Whether the key starts with this name.
+    pub fn is_prefix_of(self, key: &Key) -> bool {
+        key.0.len() >= self.0.len()
+            && key.0.as_bytes()[..self.0.len()].eq_ignore_ascii_case(self.0.as_bytes())
+    }
+}
+
+impl Hash for Uncased<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl Equivalent<Key> for Uncased<'_> {
+    fn equivalent(&self, key: &Key) -> bool {
+        self.0.eq_ignore_ascii_case(&key.0)
+    }
+}
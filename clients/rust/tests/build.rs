@@ -1,3 +1,5 @@
+#![cfg(feature = "remote")]
+
 use arma3_wiki::{Wiki, model::Call};
 
 #[test]
@@ -0,0 +1,164 @@
+use arma3_wiki::{
+    Wiki,
+    error::WikiError,
+    model::{Arg, Call, Command, EventHandlerNamespace, Game, Version},
+    search::SearchItem,
+    source::DirectorySource,
+};
+
+#[test]
+fn directory() {
+    let wiki = Wiki::load_source(&DirectorySource::new("tests/fixtures/dist")).unwrap();
+    assert_eq!(wiki.version(), &Version::new(2, 18));
+    assert_eq!(
+        wiki.commands().get("setdamage").unwrap().name(),
+        "setDamage"
+    );
+    assert!(wiki.commands().get("player").is_some());
+    assert_eq!(
+        wiki.event_handler("AnimChanged")[0].0,
+        EventHandlerNamespace::Standard
+    );
+}
+
+#[test]
+fn search() {
+    let mut wiki = Wiki::load_source(&DirectorySource::new("tests/fixtures/dist")).unwrap();
+    let hits = wiki.search("animation started");
+    assert!(matches!(
+        hits[0].item,
+        SearchItem::EventHandler(EventHandlerNamespace::Standard, h) if h.id() == "AnimChanged"
+    ));
+    assert!(matches!(
+        wiki.search("controlled by player")[0].item,
+        SearchItem::Command(c) if c.name() == "player"
+    ));
+
+    wiki.remove_command("player");
+    assert!(
+        !wiki
+            .search("controlled by player")
+            .iter()
+            .any(|h| matches!(h.item, SearchItem::Command(c) if c.name() == "player"))
+    );
+}
+
+#[test]
+fn at_version() {
+    let wiki = Wiki::load_source(&DirectorySource::new("tests/fixtures/dist")).unwrap();
+    let set_damage = wiki.commands().get("setDamage").unwrap();
+    assert_eq!(set_damage.syntax().len(), 2);
+    // optional arguments are written as plain names, and marked again from the params when read
+    let yaml = serde_yaml::to_string(set_damage).unwrap();
+    assert!(!yaml.contains("optional: useEffects"));
+    assert_eq!(&serde_yaml::from_str::<Command>(&yaml).unwrap(), set_damage);
+
+    let old = wiki.at_version(Game::Arma3, &Version::new(1, 0));
+    assert_eq!(old.commands().get("setDamage").unwrap().syntax().len(), 1);
+    assert_eq!(old.event_handler("AnimChanged").len(), 1);
+
+    let recent = wiki.at_version(Game::Arma3, &Version::new(2, 0));
+    let syntax = &recent.commands().get("setDamage").unwrap().syntax()[1];
+    assert_eq!(syntax.params().len(), 3);
+    let Call::Binary(_, Arg::Array(args)) = syntax.call() else {
+        panic!("expected an array argument");
+    };
+    assert_eq!(args.len(), 2);
+
+    assert!(
+        wiki.at_version(Game::Arma3, &Version::new(0, 40))
+            .commands()
+            .get("player")
+            .is_none()
+    );
+}
+
+#[test]
+fn at_version_games() {
+    let wiki = Wiki::load_source(&DirectorySource::new("tests/fixtures/games")).unwrap();
+    let arma_2 = wiki.at_version(Game::Arma2, &Version::new(1, 0));
+    assert_eq!(
+        arma_2.commands().get("setDamage").unwrap().syntax().len(),
+        1
+    );
+    // versions are not carried over to games without one
+    assert!(
+        wiki.at_version(Game::Argo, &Version::new(1, 0))
+            .commands()
+            .get("setDamage")
+            .is_none()
+    );
+}
+
+#[test]
+fn required_version() {
+    let wiki = Wiki::load_source(&DirectorySource::new("tests/fixtures/dist")).unwrap();
+    let required = wiki.required_version("// a comment\nplayer setDamage [1, false, objNull];");
+    assert_eq!(required.version(), Some(Version::new(2, 14)));
+    let offending = required.offending().collect::<Vec<_>>();
+    assert_eq!(offending.len(), 1);
+    assert_eq!(offending[0].item.to_string(), "setDamage (param `killer`)");
+    assert_eq!(offending[0].offset, 20);
+
+    assert_eq!(
+        wiki.required_version("player setDamage 1").version(),
+        Some(Version::new(0, 50))
+    );
+}
+
+#[test]
+fn partial() {
+    let source = DirectorySource::new("tests/fixtures/broken");
+    assert!(Wiki::load_source(&source).is_err());
+
+    let (wiki, diagnostics) = Wiki::load_source_partial(&source).unwrap();
+    assert!(wiki.commands().get("player").is_some());
+    assert_eq!(diagnostics.len(), 2);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| matches!(d, WikiError::Yaml { line: Some(_), .. }))
+    );
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| matches!(d, WikiError::Namespace { namespace, .. } if namespace == "unknown"))
+    );
+}
+
+#[test]
+fn missing() {
+    assert!(matches!(
+        Wiki::load_source(&DirectorySource::new("tests/fixtures/missing")),
+        Err(WikiError::Io { .. })
+    ));
+}
+
+#[test]
+fn dist() {
+    let wiki = Wiki::load_dist().unwrap();
+    assert_eq!(Some(*wiki.version()), Wiki::dist_version());
+    // strict loading succeeded, so the build script left nothing out
+    let (_, diagnostics) = Wiki::load_dist_partial().unwrap();
+    assert!(diagnostics.is_empty());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn dist_matches_yaml() {
+    let snapshot = Wiki::load_dist().unwrap();
+    let yaml = Wiki::load_source(&arma3_wiki::source::EmbeddedSource).unwrap();
+    assert_eq!(snapshot.version(), yaml.version());
+    assert_eq!(snapshot.commands().len(), yaml.commands().len());
+    for (name, command) in yaml.commands().iter() {
+        assert_eq!(snapshot.commands().get(name), Some(command));
+    }
+    for (ns, handlers) in yaml.event_handlers().iter() {
+        for handler in handlers {
+            assert_eq!(
+                snapshot.event_handlers().get(ns, handler.id()),
+                Some(handler)
+            );
+        }
+    }
+}